@@ -0,0 +1,3 @@
+-- W3C trace context captured at submit time, restored as the parent of
+-- the work execution span so traces cross the submit/execute boundary.
+ALTER TABLE work_items ADD COLUMN trace_context JSONB;
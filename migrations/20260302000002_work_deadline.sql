@@ -0,0 +1,6 @@
+-- Optional deadline after which work is no longer useful. Overdue items
+-- are surfaced by Db::list_overdue and counted when picked up late.
+ALTER TABLE work_items ADD COLUMN deadline TIMESTAMPTZ;
+
+CREATE INDEX idx_work_deadline ON work_items(deadline)
+    WHERE deadline IS NOT NULL AND state NOT IN ('completed', 'dead', 'merged');
@@ -0,0 +1,9 @@
+-- Free-form labels on work items (e.g. "team:infra", "env:prod") for
+-- filtering. Tags go with their item when it is purged.
+CREATE TABLE work_item_tags (
+    work_id     UUID NOT NULL REFERENCES work_items(id) ON DELETE CASCADE,
+    tag         TEXT NOT NULL,
+    PRIMARY KEY (work_id, tag)
+);
+
+CREATE INDEX idx_work_item_tags_tag ON work_item_tags(tag);
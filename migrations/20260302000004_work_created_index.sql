@@ -0,0 +1,2 @@
+-- Keyset pagination over (created_at DESC, id DESC).
+CREATE INDEX idx_work_created ON work_items(created_at DESC, id DESC);
@@ -0,0 +1,7 @@
+-- Optional embedding per work item for semantic dedup: a submission with
+-- no structural duplicate merges into the most similar active item of the
+-- same faculty when similarity clears the configured threshold.
+ALTER TABLE work_items ADD COLUMN embedding vector(1536);
+
+CREATE INDEX idx_work_embedding ON work_items
+    USING hnsw (embedding vector_cosine_ops);
@@ -0,0 +1,7 @@
+-- Client-supplied idempotency key: a repeated submit with the same key
+-- within the idempotency window returns the original item instead of
+-- creating or merging a new one. Matches items in any state.
+ALTER TABLE work_items ADD COLUMN idempotency_key TEXT;
+
+CREATE INDEX idx_work_idempotency ON work_items (idempotency_key, created_at)
+    WHERE idempotency_key IS NOT NULL;
@@ -0,0 +1,13 @@
+-- One row per execution attempt: opened when the item enters running,
+-- closed when it leaves. Attempt numbers keep counting across operator
+-- replays, which reset work_items.attempts. History goes with its item
+-- when it is purged.
+CREATE TABLE work_attempts (
+    work_id     UUID NOT NULL REFERENCES work_items(id) ON DELETE CASCADE,
+    attempt_no  INTEGER NOT NULL,
+    started_at  TIMESTAMPTZ NOT NULL,
+    ended_at    TIMESTAMPTZ,
+    outcome     TEXT,
+    error       TEXT,
+    PRIMARY KEY (work_id, attempt_no)
+);
@@ -0,0 +1,11 @@
+-- Durable work-scoped log lines (e.g. hook output captured by a focus),
+-- oldest first by id. Logs go with their item when it is purged.
+CREATE TABLE work_logs (
+    id          BIGSERIAL PRIMARY KEY,
+    work_id     UUID NOT NULL REFERENCES work_items(id) ON DELETE CASCADE,
+    level       TEXT NOT NULL,
+    message     TEXT NOT NULL,
+    created_at  TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_work_logs_work ON work_logs(work_id, id);
@@ -0,0 +1,12 @@
+-- Audit trail of work item lifecycle events, in commit order by seq.
+-- Written in the same transaction as the change it records. No foreign
+-- key: the trail outlives purged items.
+CREATE TABLE work_events (
+    seq         BIGSERIAL PRIMARY KEY,
+    work_id     UUID NOT NULL,
+    kind        TEXT NOT NULL,
+    data        JSONB NOT NULL,
+    created_at  TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_work_events_work ON work_events(work_id, seq);
@@ -0,0 +1,14 @@
+-- Optional dedup scope: when set it replaces faculty in the structural
+-- dedup match, so items of different faculties sharing scope and key merge.
+ALTER TABLE work_items ADD COLUMN dedup_scope TEXT;
+
+-- Unscoped items dedup on (faculty, dedup_key) as before
+DROP INDEX IF EXISTS idx_work_dedup;
+CREATE UNIQUE INDEX idx_work_dedup ON work_items(faculty, dedup_key)
+    WHERE dedup_key IS NOT NULL AND dedup_scope IS NULL
+    AND state NOT IN ('completed', 'dead', 'merged');
+
+-- Scoped items dedup on (dedup_scope, dedup_key), whatever their faculty
+CREATE UNIQUE INDEX idx_work_dedup_scope ON work_items(dedup_scope, dedup_key)
+    WHERE dedup_key IS NOT NULL AND dedup_scope IS NOT NULL
+    AND state NOT IN ('completed', 'dead', 'merged');
@@ -0,0 +1,6 @@
+-- Optional expiry per work item: past expires_at, queued or failed work is
+-- dead-lettered instead of run.
+ALTER TABLE work_items ADD COLUMN expires_at TIMESTAMPTZ;
+
+CREATE INDEX idx_work_expires ON work_items(expires_at)
+    WHERE expires_at IS NOT NULL AND state NOT IN ('completed', 'dead', 'merged');
@@ -0,0 +1,3 @@
+-- Reads that found no faculty for the item, counted apart from pgmq's read_ct
+-- so retries, pauses and accept-filter skips don't count toward dead-lettering.
+ALTER TABLE work_items ADD COLUMN unroutable_reads INTEGER NOT NULL DEFAULT 0;
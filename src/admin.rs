@@ -0,0 +1,246 @@
+//! Embedded HTTP admin API for `animus serve` (feature `admin`).
+//!
+//! A thin JSON layer over [`Db`] and [`ControlPlane`] so operators and
+//! small UIs can inspect and submit work against the running daemon's pool
+//! instead of reconnecting per CLI command:
+//!
+//! - `GET /health` — [`ControlPlane::health`]; 503 when not ready
+//! - `GET /work?state=&faculty=&limit=` — newest work items first
+//! - `GET /work/{id}` — one work item
+//! - `GET /work/{id}/logs` — the item's log lines, oldest first
+//! - `POST /work` — submit a [`SubmitRequest`]
+//! - `GET /events?since=&limit=` — event log entries after `since`, in `seq`
+//!   order; poll with the last `seq` seen to follow the log
+//!
+//! There is no authentication: bind it to localhost or a private network.
+
+use crate::db::Db;
+use crate::db::work::{SubmitResult, WorkItemFilter};
+use crate::engine::ControlPlane;
+use crate::error::{Error, Result};
+use crate::model::work::{NewWorkItem, State, WorkId};
+use http_body_util::{BodyExt, Full};
+use hyper::body::{Bytes, Incoming};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Method, Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use serde::Deserialize;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tracing::{debug, info};
+
+/// Most items `GET /work` or `GET /events` returns, whatever `limit` asks
+/// for.
+const MAX_LIST_LIMIT: i64 = 1000;
+
+/// Events `GET /events` returns when no `limit` is given.
+const DEFAULT_EVENTS_LIMIT: i64 = 100;
+
+/// Body of `POST /work`; mirrors `animus work submit`.
+#[derive(Debug, Deserialize)]
+pub struct SubmitRequest {
+    pub faculty: String,
+    pub source: String,
+    pub skill: Option<String>,
+    pub dedup_key: Option<String>,
+    pub dedup_scope: Option<String>,
+    pub trigger: Option<String>,
+    #[serde(default)]
+    pub params: Option<serde_json::Value>,
+    #[serde(default)]
+    pub priority: Option<i32>,
+    pub queue: Option<String>,
+}
+
+impl SubmitRequest {
+    fn into_new_work_item(self) -> NewWorkItem {
+        let mut new = NewWorkItem::new(self.faculty, self.source)
+            .params(self.params.unwrap_or_else(|| serde_json::json!({})));
+        if let Some(priority) = self.priority {
+            new = new.priority(priority);
+        }
+        if let Some(queue) = self.queue {
+            new = new.queue(queue);
+        }
+        if let Some(skill) = self.skill {
+            new = new.skill(skill);
+        }
+        if let Some(key) = self.dedup_key {
+            new = new.dedup_key(key);
+        }
+        if let Some(scope) = self.dedup_scope {
+            new = new.dedup_scope(scope);
+        }
+        if let Some(trigger) = self.trigger {
+            new = new.trigger(trigger);
+        }
+        new
+    }
+}
+
+/// Accept connections on `addr` until the task is dropped.
+pub async fn serve(addr: SocketAddr, db: Arc<Db>, control: ControlPlane) -> Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    info!("admin API listening on {}", listener.local_addr()?);
+    loop {
+        let (stream, peer) = listener.accept().await?;
+        let db = Arc::clone(&db);
+        let control = control.clone();
+        tokio::spawn(async move {
+            let service = service_fn(move |req| {
+                let db = Arc::clone(&db);
+                let control = control.clone();
+                async move { Ok::<_, std::convert::Infallible>(route(req, &db, &control).await) }
+            });
+            if let Err(e) = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await
+            {
+                debug!("admin connection from {peer} ended: {e}");
+            }
+        });
+    }
+}
+
+async fn route(req: Request<Incoming>, db: &Db, control: &ControlPlane) -> Response<Full<Bytes>> {
+    let path = req.uri().path().trim_end_matches('/').to_string();
+    let query = req.uri().query().unwrap_or("").to_string();
+    let segments: Vec<&str> = path.split('/').skip(1).collect();
+
+    let result = match (req.method(), segments.as_slice()) {
+        (&Method::GET, ["health"]) => {
+            let report = control.health().await;
+            let status = if report.ready {
+                StatusCode::OK
+            } else {
+                StatusCode::SERVICE_UNAVAILABLE
+            };
+            return json(status, &report);
+        }
+        (&Method::GET, ["work"]) => list_work(db, &query).await,
+        (&Method::GET, ["work", id]) => match parse_id(id) {
+            Ok(id) => db
+                .get_work_item(id)
+                .await
+                .map(|item| json(StatusCode::OK, &item)),
+            Err(e) => Err(e),
+        },
+        (&Method::GET, ["work", id, "logs"]) => match parse_id(id) {
+            Ok(id) => work_logs(db, id).await,
+            Err(e) => Err(e),
+        },
+        (&Method::POST, ["work"]) => submit_work(db, req).await,
+        (&Method::GET, ["events"]) => list_events(db, &query).await,
+        _ => return error_response(StatusCode::NOT_FOUND, "no such endpoint"),
+    };
+    result.unwrap_or_else(|e| error_response(status_for(&e), &e.to_string()))
+}
+
+async fn list_work(db: &Db, query: &str) -> Result<Response<Full<Bytes>>> {
+    let mut filter = WorkItemFilter::new();
+    for (key, value) in form_urlencoded::parse(query.as_bytes()) {
+        filter = match key.as_ref() {
+            "state" => filter.state(
+                value
+                    .parse::<State>()
+                    .map_err(|_| Error::InvalidState(format!("invalid state: {value}")))?,
+            ),
+            "faculty" => filter.faculty(value.as_ref()),
+            "limit" => filter.limit(
+                value
+                    .parse::<i64>()
+                    .map_err(|_| Error::InvalidState(format!("invalid limit: {value}")))?
+                    .clamp(1, MAX_LIST_LIMIT),
+            ),
+            _ => filter,
+        };
+    }
+    let items = db.query_work_items(&filter).await?;
+    Ok(json(StatusCode::OK, &items))
+}
+
+async fn work_logs(db: &Db, id: WorkId) -> Result<Response<Full<Bytes>>> {
+    // An unknown item is a 404, not an empty log
+    db.get_work_item(id).await?;
+    let logs = db.get_logs(id).await?;
+    Ok(json(StatusCode::OK, &logs))
+}
+
+async fn list_events(db: &Db, query: &str) -> Result<Response<Full<Bytes>>> {
+    let (mut since, mut limit) = (0, DEFAULT_EVENTS_LIMIT);
+    for (key, value) in form_urlencoded::parse(query.as_bytes()) {
+        match key.as_ref() {
+            "since" => {
+                since = value
+                    .parse::<i64>()
+                    .map_err(|_| Error::InvalidState(format!("invalid since: {value}")))?
+            }
+            "limit" => {
+                limit = value
+                    .parse::<i64>()
+                    .map_err(|_| Error::InvalidState(format!("invalid limit: {value}")))?
+                    .clamp(1, MAX_LIST_LIMIT)
+            }
+            _ => {}
+        }
+    }
+    let events = db.get_events_since(since, limit).await?;
+    Ok(json(StatusCode::OK, &events))
+}
+
+async fn submit_work(db: &Db, req: Request<Incoming>) -> Result<Response<Full<Bytes>>> {
+    let body = req
+        .into_body()
+        .collect()
+        .await
+        .map_err(|e| Error::Other(format!("read request body: {e}")))?
+        .to_bytes();
+    let request: SubmitRequest = serde_json::from_slice(&body)
+        .map_err(|e| Error::InvalidState(format!("invalid submit request: {e}")))?;
+
+    Ok(match db.submit_work(request.into_new_work_item()).await? {
+        SubmitResult::Created(item) => json(StatusCode::CREATED, &item),
+        SubmitResult::Merged {
+            new_id,
+            canonical_id,
+        } => json(
+            StatusCode::OK,
+            &serde_json::json!({ "merged": new_id, "canonical_id": canonical_id }),
+        ),
+        SubmitResult::AlreadyExists { id } => {
+            json(StatusCode::OK, &serde_json::json!({ "already_exists": id }))
+        }
+    })
+}
+
+fn parse_id(id: &str) -> Result<WorkId> {
+    uuid::Uuid::parse_str(id)
+        .map(WorkId)
+        .map_err(|_| Error::InvalidState(format!("invalid work id: {id}")))
+}
+
+/// Client mistakes map to 4xx; everything else is the server's problem.
+fn status_for(err: &Error) -> StatusCode {
+    match err {
+        Error::NotFound { .. } => StatusCode::NOT_FOUND,
+        Error::InvalidState(_) | Error::InvalidTransition { .. } | Error::Config(_) => {
+            StatusCode::BAD_REQUEST
+        }
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response<Full<Bytes>> {
+    json(status, &serde_json::json!({ "error": message }))
+}
+
+fn json(status: StatusCode, body: &impl serde::Serialize) -> Response<Full<Bytes>> {
+    let body = serde_json::to_vec(body).unwrap_or_default();
+    Response::builder()
+        .status(status)
+        .header("content-type", "application/json")
+        .body(Full::new(Bytes::from(body)))
+        .expect("static response parts are valid")
+}
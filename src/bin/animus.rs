@@ -2,11 +2,12 @@
 
 use animus_rs::config::Config;
 use animus_rs::db::Db;
-use animus_rs::engine::{ControlConfig, ControlPlane};
+use animus_rs::db::work::WorkCursor;
+use animus_rs::engine::{ControlConfig, ControlPlane, QueueConfig};
 use animus_rs::faculty::FacultyRegistry;
-use animus_rs::model::work::{NewWorkItem, State};
+use animus_rs::model::work::{NewWorkItem, PriorityLevel, State, WorkId};
 use animus_rs::telemetry::{TelemetryConfig, init_telemetry};
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use secrecy::ExposeSecret;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -14,10 +15,19 @@ use std::sync::Arc;
 #[derive(Parser)]
 #[command(name = "animus", about = "Substrate for relational beings")]
 struct Cli {
+    /// Output format for list/show/logs commands
+    #[arg(long, value_enum, default_value_t = OutputFormat::Table, global = true)]
+    output: OutputFormat,
     #[command(subcommand)]
     command: Command,
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    Table,
+    Json,
+}
+
 #[derive(Subcommand)]
 enum Command {
     /// Run the control plane daemon
@@ -28,12 +38,41 @@ enum Command {
         /// Global maximum concurrent foci
         #[arg(long, default_value_t = 4)]
         max_concurrent: usize,
+        /// Queue to consume, optionally capped as NAME=MAX (repeatable)
+        #[arg(long = "queue", default_value = "work")]
+        queues: Vec<String>,
+        /// Only serve work for this type (repeatable); default all types
+        #[arg(long = "accept-type")]
+        accept_types: Vec<String>,
+        /// Never serve work for this type (repeatable)
+        #[arg(long = "reject-type")]
+        reject_types: Vec<String>,
+        /// Serve the HTTP admin API on this address (e.g. 127.0.0.1:8080)
+        #[cfg(feature = "admin")]
+        #[arg(long)]
+        admin_addr: Option<std::net::SocketAddr>,
     },
     /// Work item operations
     Work {
         #[command(subcommand)]
         action: WorkAction,
     },
+    /// Faculty config inspection
+    Faculty {
+        /// Directory containing faculty TOML configs
+        #[arg(long, default_value = "faculties", global = true)]
+        faculties: PathBuf,
+        #[command(subcommand)]
+        action: FacultyAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum FacultyAction {
+    /// Show each loaded faculty and its hooks
+    List,
+    /// Check every faculty config and hook; exits non-zero on problems
+    Validate,
 }
 
 #[derive(Subcommand)]
@@ -50,15 +89,22 @@ enum WorkAction {
         /// Structural dedup key
         #[arg(long)]
         dedup_key: Option<String>,
+        /// Dedup against any faculty's work sharing this scope and key
+        #[arg(long, requires = "dedup_key")]
+        dedup_scope: Option<String>,
         /// Provenance trigger info
         #[arg(long)]
         trigger: Option<String>,
         /// JSON parameters
         #[arg(long)]
         params: Option<String>,
-        /// Priority (higher = more urgent)
-        #[arg(long, default_value_t = 0)]
-        priority: i32,
+        /// Priority (higher = more urgent): a number or low/normal/high/critical.
+        /// Defaults to the faculty's base priority
+        #[arg(long, value_parser = parse_priority)]
+        priority: Option<i32>,
+        /// Target queue
+        #[arg(long, default_value = "work")]
+        queue: String,
     },
     /// List work items
     List {
@@ -71,12 +117,41 @@ enum WorkAction {
         /// Maximum items to show
         #[arg(long, default_value_t = 20)]
         limit: i64,
+        /// Continue after this cursor (printed at the end of a full page)
+        #[arg(long)]
+        after: Option<WorkCursor>,
     },
     /// Show a work item
     Show {
         /// Work item ID (full UUID or prefix)
         id: String,
     },
+    /// Show a work item's durable log (captured hook output)
+    Logs {
+        /// Work item ID (full UUID or prefix)
+        id: String,
+    },
+    /// Re-queue a dead or failed work item for a fresh run
+    Retry {
+        /// Work item ID (full UUID or prefix)
+        id: String,
+    },
+    /// Cancel a queued work item (moves it to dead)
+    Cancel {
+        /// Work item ID (full UUID or prefix)
+        id: String,
+        /// Reason recorded as the outcome error
+        #[arg(long, default_value = "cancelled by operator")]
+        reason: String,
+    },
+    /// Change the priority of a queued work item
+    Bump {
+        /// Work item ID (full UUID or prefix)
+        id: String,
+        /// New priority (higher = more urgent): a number or low/normal/high/critical
+        #[arg(allow_hyphen_values = true, value_parser = parse_priority)]
+        priority: i32,
+    },
 }
 
 #[tokio::main]
@@ -88,7 +163,32 @@ async fn main() -> anyhow::Result<()> {
         Command::Serve {
             faculties,
             max_concurrent,
-        } => cmd_serve(faculties, max_concurrent).await,
+            queues,
+            accept_types,
+            reject_types,
+            #[cfg(feature = "admin")]
+            admin_addr,
+        } => {
+            #[cfg(not(feature = "admin"))]
+            let admin_addr = None;
+            let control = ControlConfig {
+                accept_types: (!accept_types.is_empty()).then_some(accept_types),
+                reject_types,
+                ..ControlConfig::default()
+            };
+            cmd_serve(faculties, max_concurrent, queues, control, admin_addr).await
+        }
+        Command::Faculty { faculties, action } => {
+            let registry = FacultyRegistry::load_from_dir(&faculties)?;
+            match action {
+                FacultyAction::List => cmd_faculty_list(&registry),
+                FacultyAction::Validate => {
+                    check_faculties(&registry)?;
+                    println!("{} faculty config(s) OK", registry.len());
+                    Ok(())
+                }
+            }
+        }
         Command::Work { action } => {
             let config = Config::from_env()?;
             let db = Db::connect(config.database_url.expose_secret()).await?;
@@ -101,12 +201,24 @@ async fn main() -> anyhow::Result<()> {
                     source,
                     skill,
                     dedup_key,
+                    dedup_scope,
                     trigger,
                     params,
                     priority,
+                    queue,
                 } => {
+                    db.create_queue(&queue).await?;
                     cmd_work_submit(
-                        &db, faculty, source, skill, dedup_key, trigger, params, priority,
+                        &db,
+                        faculty,
+                        source,
+                        skill,
+                        dedup_key,
+                        dedup_scope,
+                        trigger,
+                        params,
+                        priority,
+                        queue,
                     )
                     .await
                 }
@@ -114,31 +226,47 @@ async fn main() -> anyhow::Result<()> {
                     state,
                     faculty,
                     limit,
-                } => cmd_work_list(&db, state, faculty, limit).await,
-                WorkAction::Show { id } => cmd_work_show(&db, id).await,
+                    after,
+                } => cmd_work_list(&db, state, faculty, limit, after, cli.output).await,
+                WorkAction::Show { id } => cmd_work_show(&db, id, cli.output).await,
+                WorkAction::Logs { id } => cmd_work_logs(&db, id, cli.output).await,
+                WorkAction::Retry { id } => cmd_work_retry(&db, id).await,
+                WorkAction::Cancel { id, reason } => cmd_work_cancel(&db, id, reason).await,
+                WorkAction::Bump { id, priority } => cmd_work_bump(&db, id, priority).await,
             }
         }
     }
 }
 
-async fn cmd_serve(faculties: PathBuf, max_concurrent: usize) -> anyhow::Result<()> {
+async fn cmd_serve(
+    faculties: PathBuf,
+    max_concurrent: usize,
+    queues: Vec<String>,
+    control: ControlConfig,
+    admin_addr: Option<std::net::SocketAddr>,
+) -> anyhow::Result<()> {
+    let queues = queues
+        .iter()
+        .map(|spec| parse_queue(spec))
+        .collect::<anyhow::Result<Vec<_>>>()?;
     let config = Config::from_env()?;
 
-    let _guard = init_telemetry(TelemetryConfig {
-        endpoint: config.otel_endpoint.clone(),
-        service_name: "animus".to_string(),
-    })?;
+    let _guard = init_telemetry(TelemetryConfig::from_env("animus")?)?;
 
     let db = Db::connect(config.database_url.expose_secret()).await?;
     db.migrate().await?;
-    db.create_queue("work").await?;
+    for queue in &queues {
+        db.create_queue(&queue.name).await?;
+    }
 
     let registry = FacultyRegistry::load_from_dir(&faculties)?;
+    check_faculties(&registry)?;
 
+    let db = Arc::new(db);
     let control = ControlPlane::new(
-        Arc::new(db),
+        Arc::clone(&db),
         Arc::new(registry),
-        ControlConfig::default(),
+        ControlConfig { queues, ..control },
         max_concurrent,
     );
 
@@ -148,10 +276,103 @@ async fn cmd_serve(faculties: PathBuf, max_concurrent: usize) -> anyhow::Result<
         ctrl.shutdown();
     });
 
+    // SIGHUP reloads faculty configs without dropping in-flight work
+    #[cfg(unix)]
+    {
+        let ctrl = control.clone();
+        let mut hangup = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup())?;
+        tokio::spawn(async move {
+            while hangup.recv().await.is_some() {
+                if let Err(e) = ctrl.reload_faculties() {
+                    tracing::error!("faculty reload failed: {e}");
+                }
+            }
+        });
+    }
+
+    #[cfg(feature = "admin")]
+    if let Some(addr) = admin_addr {
+        let ctrl = control.clone();
+        tokio::spawn(async move {
+            if let Err(e) = animus_rs::admin::serve(addr, db, ctrl).await {
+                tracing::error!("admin API stopped: {e}");
+            }
+        });
+    }
+    #[cfg(not(feature = "admin"))]
+    let _ = admin_addr;
+
     control.run().await?;
     Ok(())
 }
 
+/// Print every hook problem in `registry` and fail if there were any.
+fn check_faculties(registry: &FacultyRegistry) -> anyhow::Result<()> {
+    if let Err(errors) = registry.validate() {
+        for e in &errors {
+            eprintln!("  - {e}");
+        }
+        anyhow::bail!("{} faculty config error(s)", errors.len());
+    }
+    Ok(())
+}
+
+fn cmd_faculty_list(registry: &FacultyRegistry) -> anyhow::Result<()> {
+    let faculties = registry.faculties();
+    if faculties.is_empty() {
+        println!("No faculties loaded.");
+        return Ok(());
+    }
+
+    for meta in faculties {
+        println!("{}", meta.name);
+        println!("  concurrent:  {}", meta.concurrent);
+        println!(
+            "  isolation:   {}",
+            meta.isolation.as_deref().unwrap_or("-")
+        );
+        let hooks = [
+            ("orient", meta.orient.as_ref()),
+            ("engage", Some(&meta.engage)),
+            ("consolidate", meta.consolidate.as_ref()),
+        ];
+        for (phase, hook) in hooks {
+            let Some(hook) = hook else { continue };
+            let timeout = hook
+                .timeout_secs
+                .map(|t| format!(" (timeout {t}s)"))
+                .unwrap_or_default();
+            println!("  {phase:<12} {}{timeout}", hook.command.display());
+        }
+        println!(
+            "  {:<12} {} (max attempts {})",
+            "recover",
+            meta.recover.command.display(),
+            meta.recover.max_attempts
+        );
+    }
+    Ok(())
+}
+
+/// Parse a priority: a raw number or a named level.
+fn parse_priority(s: &str) -> anyhow::Result<i32> {
+    match s.parse::<i32>() {
+        Ok(priority) => Ok(priority),
+        Err(_) => Ok(s.parse::<PriorityLevel>()?.value()),
+    }
+}
+
+/// Parse a `--queue` spec: `NAME` or `NAME=MAX`.
+fn parse_queue(spec: &str) -> anyhow::Result<QueueConfig> {
+    match spec.split_once('=') {
+        Some((name, max)) => Ok(QueueConfig::new(name).max_concurrent(
+            max.parse()
+                .map_err(|_| anyhow::anyhow!("invalid queue limit: {spec}"))?,
+        )),
+        None => Ok(QueueConfig::new(spec)),
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 async fn cmd_work_submit(
     db: &Db,
@@ -159,9 +380,11 @@ async fn cmd_work_submit(
     source: String,
     skill: Option<String>,
     dedup_key: Option<String>,
+    dedup_scope: Option<String>,
     trigger: Option<String>,
     params: Option<String>,
-    priority: i32,
+    priority: Option<i32>,
+    queue: String,
 ) -> anyhow::Result<()> {
     let params: serde_json::Value = match params {
         Some(json) => serde_json::from_str(&json)?,
@@ -170,14 +393,20 @@ async fn cmd_work_submit(
 
     let mut new = NewWorkItem::new(&faculty, &source)
         .params(params)
-        .priority(priority);
+        .queue(queue);
 
+    if let Some(priority) = priority {
+        new = new.priority(priority);
+    }
     if let Some(ref s) = skill {
         new = new.skill(s);
     }
     if let Some(ref key) = dedup_key {
         new = new.dedup_key(key);
     }
+    if let Some(ref scope) = dedup_scope {
+        new = new.dedup_scope(scope);
+    }
     if let Some(ref trig) = trigger {
         new = new.trigger(trig);
     }
@@ -194,6 +423,9 @@ async fn cmd_work_submit(
         } => {
             println!("Merged: {new_id} → canonical {canonical_id}");
         }
+        animus_rs::db::work::SubmitResult::AlreadyExists { id } => {
+            println!("Already submitted: {id}");
+        }
     }
 
     Ok(())
@@ -204,6 +436,8 @@ async fn cmd_work_list(
     state: Option<String>,
     faculty: Option<String>,
     limit: i64,
+    after: Option<WorkCursor>,
+    output: OutputFormat,
 ) -> anyhow::Result<()> {
     let state_filter: Option<State> = match state {
         Some(s) => Some(
@@ -213,9 +447,15 @@ async fn cmd_work_list(
         None => None,
     };
 
-    let items = db
-        .list_work_items(state_filter, faculty.as_deref(), limit)
+    let page = db
+        .list_work_items_page(state_filter, faculty.as_deref(), limit, after)
         .await?;
+    let items = page.items;
+
+    if output == OutputFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&items)?);
+        return Ok(());
+    }
 
     if items.is_empty() {
         println!("No work items found.");
@@ -249,17 +489,21 @@ async fn cmd_work_list(
     }
 
     println!("\n{} item(s)", items.len());
+    if let Some(next) = page.next {
+        println!("next page: --after {next}");
+    }
     Ok(())
 }
 
-async fn cmd_work_show(db: &Db, id_str: String) -> anyhow::Result<()> {
+/// Resolve a full work item UUID or a unique prefix of one.
+async fn resolve_work_id(db: &Db, id_str: &str) -> anyhow::Result<WorkId> {
     // Support prefix matching — find the work item whose ID starts with the given string
     let id = if id_str.len() < 36 {
         // Prefix search
         let items = db.list_work_items(None, None, 100).await?;
         let matches: Vec<_> = items
             .iter()
-            .filter(|item| item.id.to_string().starts_with(&id_str))
+            .filter(|item| item.id.to_string().starts_with(id_str))
             .collect();
         match matches.len() {
             0 => anyhow::bail!("no work item matching prefix '{id_str}'"),
@@ -267,11 +511,66 @@ async fn cmd_work_show(db: &Db, id_str: String) -> anyhow::Result<()> {
             n => anyhow::bail!("{n} work items match prefix '{id_str}' — be more specific"),
         }
     } else {
-        let uuid = uuid::Uuid::parse_str(&id_str)?;
-        animus_rs::model::work::WorkId(uuid)
+        let uuid = uuid::Uuid::parse_str(id_str)?;
+        WorkId(uuid)
     };
+    Ok(id)
+}
+
+async fn cmd_work_logs(db: &Db, id_str: String, output: OutputFormat) -> anyhow::Result<()> {
+    let id = resolve_work_id(db, &id_str).await?;
+    let logs = db.get_logs(id).await?;
+
+    if output == OutputFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&logs)?);
+        return Ok(());
+    }
 
+    for entry in &logs {
+        println!(
+            "{}  {:<5}  {}",
+            entry.created_at, entry.level, entry.message
+        );
+    }
+    Ok(())
+}
+
+async fn cmd_work_retry(db: &Db, id_str: String) -> anyhow::Result<()> {
+    let id = resolve_work_id(db, &id_str).await?;
+    let item = db.replay_dead_letter(id).await?;
+    println!("Re-queued: {} (queue: {})", item.id, item.queue);
+    Ok(())
+}
+
+async fn cmd_work_cancel(db: &Db, id_str: String, reason: String) -> anyhow::Result<()> {
+    let id = resolve_work_id(db, &id_str).await?;
+    let item = db.dead_letter(id, &reason).await?;
+    println!("Cancelled: {} ({reason})", item.id);
+    Ok(())
+}
+
+async fn cmd_work_bump(db: &Db, id_str: String, priority: i32) -> anyhow::Result<()> {
+    let id = resolve_work_id(db, &id_str).await?;
+    let item = db.bump_priority(id, priority).await?;
+    println!("Priority: {} -> {}", item.id, item.priority);
+    Ok(())
+}
+
+async fn cmd_work_show(db: &Db, id_str: String, output: OutputFormat) -> anyhow::Result<()> {
+    let id = resolve_work_id(db, &id_str).await?;
     let item = db.get_work_item(id).await?;
+    let history = db.get_attempt_history(item.id).await?;
+    let merged = db.get_merged_provenance(item.id).await?;
+
+    if output == OutputFormat::Json {
+        // The item's own fields, plus what the text view gathers around it
+        let mut json = serde_json::to_value(&item)?;
+        json["attempt_history"] = serde_json::to_value(&history)?;
+        json["merged_provenance"] = serde_json::to_value(&merged)?;
+        json["deduped"] = merged.len().into();
+        println!("{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
 
     println!("ID:         {}", item.id);
     println!("Faculty:    {}", item.faculty);
@@ -279,6 +578,9 @@ async fn cmd_work_show(db: &Db, id_str: String) -> anyhow::Result<()> {
     println!("State:      {}", item.state);
     println!("Priority:   {}", item.priority);
     println!("Dedup Key:  {}", item.dedup_key.as_deref().unwrap_or("-"));
+    if let Some(ref scope) = item.dedup_scope {
+        println!("Dedup Scope: {scope}");
+    }
     println!("Source:     {}", item.provenance.source);
     println!(
         "Trigger:    {}",
@@ -289,6 +591,17 @@ async fn cmd_work_show(db: &Db, id_str: String) -> anyhow::Result<()> {
         serde_json::to_string_pretty(&item.params)?
     );
     println!("Attempts:   {}", item.attempts);
+    for attempt in &history {
+        println!(
+            "  #{}  {}  {}  {}",
+            attempt.attempt,
+            attempt.started_at,
+            attempt
+                .outcome
+                .map_or("running".to_string(), |s| s.to_string()),
+            attempt.error.as_deref().unwrap_or("")
+        );
+    }
     println!(
         "Max Tries:  {}",
         item.max_attempts
@@ -306,6 +619,16 @@ async fn cmd_work_show(db: &Db, id_str: String) -> anyhow::Result<()> {
     if let Some(merged) = item.merged_into {
         println!("Merged Into: {merged}");
     }
+    println!("Deduped:    {}", merged.len());
+    for entry in &merged {
+        println!(
+            "  {}  {} {}  {}",
+            entry.id,
+            entry.provenance.source,
+            entry.provenance.trigger.as_deref().unwrap_or("-"),
+            entry.created_at
+        );
+    }
     if let Some(ref outcome) = item.outcome {
         println!("---");
         println!(
@@ -4,25 +4,163 @@
 //! and rig-postgres VectorStoreIndex.
 
 pub mod pgmq;
+pub mod snapshot;
 pub mod work;
 
+use crate::clock::{Clock, SystemClock};
 use crate::error::Result;
+use crate::model::work::{DEFAULT_MAX_PARENT_DEPTH, PriorityScheme, WorkTypeDefaults};
 use sqlx::PgPool;
 use sqlx::postgres::PgPoolOptions;
+use std::collections::HashMap;
+use std::sync::Arc;
 
 /// Database handle. Owns the connection pool shared across all modules.
 pub struct Db {
     pool: PgPool,
+    /// Minimum cosine similarity for semantic dedup on submit. None = off.
+    semantic_dedup_threshold: Option<f64>,
+    /// How long an idempotency key keeps matching its original submission.
+    idempotency_window: std::time::Duration,
+    /// Reject submissions whose params aren't a JSON object.
+    strict_params: bool,
+    /// Priority and attempt defaults per faculty, for submissions that
+    /// don't set them.
+    work_type_defaults: HashMap<String, WorkTypeDefaults>,
+    /// Source of the work item timestamps this handle writes.
+    clock: Arc<dyn Clock>,
+    /// Deepest parent chain a submission may extend to.
+    max_parent_depth: u32,
+    /// Range every stored priority must fall in. None = any i32.
+    priority_scheme: Option<PriorityScheme>,
+}
+
+/// Connection pool settings for [`Db::connect_with`].
+///
+/// The pool is shared by the control plane loop, every running focus, and
+/// the NOTIFY listener, so `max_connections` should cover the focus
+/// concurrency plus a few spare. A short `acquire_timeout` surfaces pool
+/// exhaustion as errors instead of stalls; `statement_timeout` bounds
+/// runaway queries at the cost of failing legitimately slow ones (e.g.
+/// large purges).
+#[derive(Debug, Clone)]
+pub struct DbOptions {
+    pub max_connections: u32,
+    pub min_connections: u32,
+    /// How long to wait for a free connection before erroring.
+    pub acquire_timeout: std::time::Duration,
+    /// Close connections idle this long. None = keep them.
+    pub idle_timeout: Option<std::time::Duration>,
+    /// Server-side `statement_timeout` for every connection. None = server default.
+    pub statement_timeout: Option<std::time::Duration>,
+}
+
+impl Default for DbOptions {
+    fn default() -> Self {
+        Self {
+            max_connections: 10,
+            min_connections: 0,
+            acquire_timeout: std::time::Duration::from_secs(30),
+            idle_timeout: Some(std::time::Duration::from_secs(600)),
+            statement_timeout: None,
+        }
+    }
 }
 
 impl Db {
     /// Connect to Postgres and create a connection pool.
     pub async fn connect(url: &str) -> Result<Self> {
+        Self::connect_with(url, DbOptions::default()).await
+    }
+
+    /// Connect with explicit pool settings.
+    pub async fn connect_with(url: &str, options: DbOptions) -> Result<Self> {
+        let statement_timeout = options.statement_timeout.map(|t| t.as_millis());
         let pool = PgPoolOptions::new()
-            .max_connections(10)
+            .max_connections(options.max_connections)
+            .min_connections(options.min_connections)
+            .acquire_timeout(options.acquire_timeout)
+            .idle_timeout(options.idle_timeout)
+            .after_connect(move |conn, _meta| {
+                Box::pin(async move {
+                    if let Some(ms) = statement_timeout {
+                        sqlx::query(&format!("SET statement_timeout = {ms}"))
+                            .execute(conn)
+                            .await?;
+                    }
+                    Ok(())
+                })
+            })
             .connect(url)
             .await?;
-        Ok(Self { pool })
+        Ok(Self {
+            pool,
+            semantic_dedup_threshold: None,
+            idempotency_window: std::time::Duration::from_secs(3600),
+            strict_params: false,
+            work_type_defaults: HashMap::new(),
+            clock: Arc::new(SystemClock),
+            max_parent_depth: DEFAULT_MAX_PARENT_DEPTH,
+            priority_scheme: None,
+        })
+    }
+
+    /// Merge submissions carrying an embedding into the most similar active
+    /// item of the same faculty when cosine similarity is at least
+    /// `similarity` (e.g. 0.95). Structural dedup still takes precedence.
+    pub fn with_semantic_dedup_threshold(mut self, similarity: f64) -> Self {
+        self.semantic_dedup_threshold = Some(similarity);
+        self
+    }
+
+    /// How long a submission's idempotency key keeps returning it on
+    /// resubmit. Defaults to one hour.
+    pub fn with_idempotency_window(mut self, window: std::time::Duration) -> Self {
+        self.idempotency_window = window;
+        self
+    }
+
+    /// Validate submissions with
+    /// [`NewWorkItem::validate_strict`](crate::model::work::NewWorkItem::validate_strict),
+    /// rejecting params that aren't a JSON object.
+    pub fn with_strict_params(mut self) -> Self {
+        self.strict_params = true;
+        self
+    }
+
+    /// Give submissions to each faculty its base priority and max attempts
+    /// when the submitter didn't set them. Explicit values still win.
+    pub fn with_work_type_defaults(mut self, defaults: HashMap<String, WorkTypeDefaults>) -> Self {
+        self.work_type_defaults = defaults;
+        self
+    }
+
+    /// Reject submissions whose parent chain, counting the new item, would
+    /// be deeper than `depth`. Defaults to
+    /// [`DEFAULT_MAX_PARENT_DEPTH`](crate::model::work::DEFAULT_MAX_PARENT_DEPTH).
+    pub fn with_max_parent_depth(mut self, depth: u32) -> Self {
+        self.max_parent_depth = depth;
+        self
+    }
+
+    /// Reject submissions and reprioritizations whose priority, after
+    /// faculty defaults are applied, falls outside `scheme`.
+    pub fn with_priority_scheme(mut self, scheme: PriorityScheme) -> Self {
+        self.priority_scheme = Some(scheme);
+        self
+    }
+
+    /// Stamp work item timestamps, and judge deadlines and retention, by
+    /// `clock` instead of the system clock (e.g. a
+    /// [`MockClock`](crate::clock::MockClock) in tests).
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// The clock this handle stamps work items with.
+    pub fn clock(&self) -> &dyn Clock {
+        self.clock.as_ref()
     }
 
     /// Run all pending migrations.
@@ -40,6 +178,44 @@ impl Db {
         Ok(())
     }
 
+    /// Whether the pgmq extension is installed in this database.
+    pub async fn pgmq_installed(&self) -> Result<bool> {
+        let (installed,): (bool,) =
+            sqlx::query_as("SELECT EXISTS (SELECT 1 FROM pg_extension WHERE extname = 'pgmq')")
+                .fetch_one(&self.pool)
+                .await?;
+        Ok(installed)
+    }
+
+    /// Number of embedded migrations not yet successfully applied.
+    pub async fn pending_migrations(&self) -> Result<usize> {
+        let applied: Vec<(i64,)> =
+            sqlx::query_as("SELECT version FROM _sqlx_migrations WHERE success")
+                .fetch_all(&self.pool)
+                .await?;
+        let applied: std::collections::HashSet<i64> = applied.into_iter().map(|(v,)| v).collect();
+        Ok(sqlx::migrate!("./migrations")
+            .iter()
+            .filter(|m| m.migration_type.is_up_migration() && !applied.contains(&m.version))
+            .count())
+    }
+
+    /// Vacuum and analyze the work and memory tables.
+    ///
+    /// Intended to run on a schedule after [`purge_completed`](Self::purge_completed)
+    /// so freed rows are reclaimed and planner statistics stay current.
+    /// `VACUUM` cannot run inside a transaction, so this goes straight to the
+    /// pool rather than through one. Plain `VACUUM` does not block reads or
+    /// writes, but it does add I/O load while it runs.
+    pub async fn maintenance(&self) -> Result<()> {
+        sqlx::query(
+            "VACUUM (ANALYZE) work_items, work_item_tags, work_attempts, work_logs, work_events, memories",
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
     /// Get a reference to the connection pool.
     pub fn pool(&self) -> &PgPool {
         &self.pool
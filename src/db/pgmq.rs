@@ -3,9 +3,138 @@
 //! Calls pgmq's SQL functions: pgmq.create, pgmq.send, pgmq.read,
 //! pgmq.archive, pgmq.delete.
 
-use crate::error::Result;
+use crate::error::{Error, Result};
+use crate::model::work::WorkId;
 use crate::telemetry::metrics;
 use opentelemetry::KeyValue;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Version of [`WorkPayload`] this build writes.
+pub const WORK_PAYLOAD_VERSION: u8 = 1;
+
+/// Body of the queue message sent for each queued work item.
+///
+/// Payloads written before versioning carry no `v` and read as version 1.
+/// Fields added by newer writers are ignored, so a newer payload is still
+/// served as long as it names its work item.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkPayload {
+    #[serde(default = "initial_payload_version")]
+    pub v: u8,
+    pub work_item_id: Uuid,
+    /// Routing key for conditional reads (see `claim_work_context_matching`).
+    #[serde(default)]
+    pub faculty: String,
+    /// The item's priority when the message was sent, so reads can take
+    /// higher priorities first (see `read_batch_from_queue`).
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default)]
+    pub params: serde_json::Value,
+}
+
+fn initial_payload_version() -> u8 {
+    1
+}
+
+impl WorkPayload {
+    pub fn new(
+        id: WorkId,
+        faculty: impl Into<String>,
+        priority: i32,
+        params: serde_json::Value,
+    ) -> Self {
+        Self {
+            v: WORK_PAYLOAD_VERSION,
+            work_item_id: id.0,
+            faculty: faculty.into(),
+            priority,
+            params,
+        }
+    }
+
+    /// Parse a message body, failing on anything that doesn't identify a
+    /// work item.
+    pub fn parse(message: &serde_json::Value) -> Result<Self> {
+        Self::deserialize(message)
+            .map_err(|e| Error::InvalidState(format!("bad pgmq payload: {e}")))
+    }
+
+    pub fn work_id(&self) -> WorkId {
+        WorkId(self.work_item_id)
+    }
+}
+
+/// The priorities to read `queue` in, highest first: one tier per priority
+/// above the lowest held by its queued work, then `None` for everything
+/// else (the lowest priority, and messages sent without one).
+pub(super) async fn priority_tiers(
+    conn: &mut sqlx::PgConnection,
+    queue: &str,
+) -> Result<Vec<Option<i32>>> {
+    let mut priorities: Vec<i32> = sqlx::query_scalar(
+        "SELECT DISTINCT priority FROM work_items
+         WHERE queue_name = $1 AND state = 'queued'
+         ORDER BY priority DESC",
+    )
+    .bind(queue)
+    .fetch_all(&mut *conn)
+    .await?;
+    priorities.pop();
+    Ok(priorities.into_iter().map(Some).chain([None]).collect())
+}
+
+/// The `pgmq.read` condition matching messages of `faculty` (any when
+/// `None`) sent at `priority` (any when `None`).
+pub(super) fn read_condition(faculty: Option<&str>, priority: Option<i32>) -> serde_json::Value {
+    let mut condition = serde_json::Map::new();
+    if let Some(faculty) = faculty {
+        condition.insert("faculty".into(), faculty.into());
+    }
+    if let Some(priority) = priority {
+        condition.insert("priority".into(), priority.into());
+    }
+    serde_json::Value::Object(condition)
+}
+
+/// Name of the queue that unparseable messages from `queue` are moved to.
+pub fn poison_queue(queue: &str) -> String {
+    format!("{queue}_poison")
+}
+
+/// Move a message to `queue`'s poison queue, recording why, and archive
+/// the original so it is never read again.
+pub(super) async fn poison(
+    conn: &mut sqlx::PgConnection,
+    queue: &str,
+    msg_id: i64,
+    message: &serde_json::Value,
+    reason: &str,
+) -> Result<()> {
+    let poison = poison_queue(queue);
+    sqlx::query("SELECT pgmq.create($1)")
+        .bind(&poison)
+        .execute(&mut *conn)
+        .await?;
+    sqlx::query("SELECT pgmq.send($1, $2, 0)")
+        .bind(&poison)
+        .bind(serde_json::json!({
+            "queue": queue,
+            "msg_id": msg_id,
+            "reason": reason,
+            "message": message,
+        }))
+        .execute(&mut *conn)
+        .await?;
+    sqlx::query("SELECT pgmq.archive($1, $2)")
+        .bind(queue)
+        .bind(msg_id)
+        .execute(&mut *conn)
+        .await?;
+    metrics::work_poisoned().add(1, &[KeyValue::new("queue", queue.to_string())]);
+    Ok(())
+}
 
 /// A message read from a pgmq queue.
 #[derive(Debug, Clone)]
@@ -18,6 +147,17 @@ pub struct PgmqMessage {
 }
 
 impl super::Db {
+    /// Whether `queue_name` has been created.
+    pub async fn queue_exists(&self, queue_name: &str) -> Result<bool> {
+        let (exists,): (bool,) = sqlx::query_as(
+            "SELECT EXISTS (SELECT 1 FROM pgmq.list_queues() WHERE queue_name = $1)",
+        )
+        .bind(queue_name)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(exists)
+    }
+
     /// Create a pgmq queue (idempotent).
     pub async fn create_queue(&self, queue_name: &str) -> Result<()> {
         sqlx::query("SELECT pgmq.create($1)")
@@ -65,43 +205,129 @@ impl super::Db {
         queue_name: &str,
         vt_seconds: i32,
     ) -> Result<Option<PgmqMessage>> {
-        let row = sqlx::query_as::<
-            _,
-            (
-                i64,
-                i32,
-                chrono::DateTime<chrono::Utc>,
-                chrono::DateTime<chrono::Utc>,
-                serde_json::Value,
-            ),
-        >(
-            "SELECT msg_id, read_ct, enqueued_at, vt, message FROM pgmq.read($1, $2, 1)"
-        )
-        .bind(queue_name)
-        .bind(vt_seconds)
-        .fetch_optional(&self.pool)
-        .await?;
+        Ok(self
+            .read_batch_from_queue(queue_name, vt_seconds, 1)
+            .await?
+            .pop())
+    }
 
-        let msg = row.map(|(msg_id, read_ct, enqueued_at, vt, message)| PgmqMessage {
-            msg_id,
-            read_ct,
-            enqueued_at,
-            vt,
-            message,
-        });
+    /// Read up to `qty` messages from a queue. Each message gets its own
+    /// visibility timeout of `vt_seconds`, exactly as if it had been read
+    /// alone. Returns an empty batch if the queue is empty.
+    ///
+    /// Higher-priority work is read first, oldest-first within a priority.
+    /// When all queued work shares one priority this is a single
+    /// round-trip; otherwise it takes one per priority tier read.
+    pub async fn read_batch_from_queue(
+        &self,
+        queue_name: &str,
+        vt_seconds: i32,
+        qty: i32,
+    ) -> Result<Vec<PgmqMessage>> {
+        let mut conn = self.pool.acquire().await?;
+        let mut rows = Vec::new();
+        for tier in priority_tiers(&mut conn, queue_name).await? {
+            let remaining = qty - rows.len() as i32;
+            if remaining <= 0 {
+                break;
+            }
+            rows.extend(
+                sqlx::query_as::<
+                    _,
+                    (
+                        i64,
+                        i32,
+                        chrono::DateTime<chrono::Utc>,
+                        chrono::DateTime<chrono::Utc>,
+                        serde_json::Value,
+                    ),
+                >(
+                    "SELECT msg_id, read_ct, enqueued_at, vt, message FROM pgmq.read($1, $2, $3, $4)",
+                )
+                .bind(queue_name)
+                .bind(vt_seconds)
+                .bind(remaining)
+                .bind(read_condition(None, tier))
+                .fetch_all(&mut *conn)
+                .await?,
+            );
+        }
+
+        let msgs: Vec<_> = rows
+            .into_iter()
+            .map(|(msg_id, read_ct, enqueued_at, vt, message)| PgmqMessage {
+                msg_id,
+                read_ct,
+                enqueued_at,
+                vt,
+                message,
+            })
+            .collect();
+
+        let queue = KeyValue::new("queue", queue_name.to_string());
+        if msgs.is_empty() {
+            metrics::queue_operations().add(1, &[queue, KeyValue::new("operation", "read_empty")]);
+        } else {
+            metrics::queue_operations().add(
+                msgs.len() as u64,
+                &[queue, KeyValue::new("operation", "read")],
+            );
+        }
 
+        Ok(msgs)
+    }
+
+    /// Hide a message for `delay_seconds` from now, keeping its read count.
+    pub async fn set_visibility(
+        &self,
+        queue_name: &str,
+        msg_id: i64,
+        delay_seconds: i32,
+    ) -> Result<()> {
+        sqlx::query("SELECT pgmq.set_vt($1, $2, $3)")
+            .bind(queue_name)
+            .bind(msg_id)
+            .bind(delay_seconds)
+            .execute(&self.pool)
+            .await?;
         metrics::queue_operations().add(
             1,
             &[
                 KeyValue::new("queue", queue_name.to_string()),
-                KeyValue::new(
-                    "operation",
-                    if msg.is_some() { "read" } else { "read_empty" },
-                ),
+                KeyValue::new("operation", "set_vt"),
             ],
         );
+        Ok(())
+    }
 
-        Ok(msg)
+    /// Renew the lease on a message being worked: it stays invisible for
+    /// `extra_seconds` from now. Fails with `NotFound` if the message is
+    /// gone, which means the lease can no longer be held.
+    pub async fn extend_visibility(
+        &self,
+        queue_name: &str,
+        msg_id: i64,
+        extra_seconds: i32,
+    ) -> Result<()> {
+        let renewed: Option<(i64,)> = sqlx::query_as("SELECT msg_id FROM pgmq.set_vt($1, $2, $3)")
+            .bind(queue_name)
+            .bind(msg_id)
+            .bind(extra_seconds)
+            .fetch_optional(&self.pool)
+            .await?;
+        metrics::queue_operations().add(
+            1,
+            &[
+                KeyValue::new("queue", queue_name.to_string()),
+                KeyValue::new("operation", "extend_vt"),
+            ],
+        );
+        match renewed {
+            Some(_) => Ok(()),
+            None => Err(Error::not_found(format!(
+                "message {msg_id} in queue {queue_name}"
+            ))),
+        }
     }
 
     /// Archive a message (moves to archive table, preserves for audit).
@@ -121,6 +347,21 @@ impl super::Db {
         Ok(())
     }
 
+    /// Move an unparseable message to [`poison_queue`]`(queue)` so it stops
+    /// being re-read. The poison message records the source queue, message
+    /// id, `reason`, and the original body.
+    pub async fn poison_message(
+        &self,
+        queue_name: &str,
+        msg: &PgmqMessage,
+        reason: &str,
+    ) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        poison(&mut tx, queue_name, msg.msg_id, &msg.message, reason).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
     /// Delete a message permanently.
     pub async fn delete_message(&self, queue_name: &str, msg_id: i64) -> Result<()> {
         sqlx::query("SELECT pgmq.delete($1, $2)")
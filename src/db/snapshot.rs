@@ -0,0 +1,316 @@
+//! Portable JSON snapshots of work state, for backups and offline debugging.
+//!
+//! Merged duplicates are rows in `work_items` (state `merged`, pointing at
+//! their canonical via `merged_into`), so a snapshot of that table carries
+//! the full work history including dedup provenance. Attempt history, work
+//! logs, and the event log travel with it, keeping their ids and `seq`
+//! numbers.
+
+use super::pgmq::WorkPayload;
+use super::work::{WORK_ITEM_COLUMNS, WorkItemRow, insert_tags};
+use crate::error::{Error, Result};
+use crate::memory::store::format_vector;
+use crate::model::work::{State, WorkItem};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use uuid::Uuid;
+
+/// Snapshot format version. Bump on incompatible changes.
+///
+/// Version 1 held work items only; it still imports, with empty history.
+const SNAPSHOT_VERSION: u32 = 2;
+
+/// Top-level snapshot document.
+#[derive(Serialize, Deserialize)]
+struct Snapshot {
+    version: u32,
+    exported_at: chrono::DateTime<chrono::Utc>,
+    work_items: Vec<SnapshotItem>,
+    #[serde(default)]
+    attempts: Vec<SnapshotAttempt>,
+    #[serde(default)]
+    logs: Vec<SnapshotLog>,
+    #[serde(default)]
+    events: Vec<SnapshotEvent>,
+}
+
+/// A work item plus the columns `WorkItem` doesn't expose.
+#[derive(Serialize, Deserialize)]
+struct SnapshotItem {
+    #[serde(flatten)]
+    item: WorkItem,
+    #[serde(default)]
+    idempotency_key: Option<String>,
+    #[serde(default)]
+    embedding: Option<Vec<f32>>,
+}
+
+/// A `work_attempts` row.
+#[derive(Serialize, Deserialize, sqlx::FromRow)]
+struct SnapshotAttempt {
+    work_id: Uuid,
+    attempt_no: i32,
+    started_at: chrono::DateTime<chrono::Utc>,
+    ended_at: Option<chrono::DateTime<chrono::Utc>>,
+    outcome: Option<String>,
+    error: Option<String>,
+}
+
+/// A `work_logs` row.
+#[derive(Serialize, Deserialize, sqlx::FromRow)]
+struct SnapshotLog {
+    id: i64,
+    work_id: Uuid,
+    level: String,
+    message: String,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// A `work_events` row, with its data exactly as stored.
+#[derive(Serialize, Deserialize, sqlx::FromRow)]
+struct SnapshotEvent {
+    seq: i64,
+    work_id: Uuid,
+    kind: String,
+    data: serde_json::Value,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl super::Db {
+    /// Write every work item to `writer` as a versioned JSON document.
+    pub async fn export_json(&self, writer: impl Write) -> Result<()> {
+        let rows: Vec<WorkItemRow> = sqlx::query_as(&format!(
+            "SELECT {WORK_ITEM_COLUMNS} FROM work_items ORDER BY created_at, id"
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+        let extra: Vec<(Uuid, Option<String>, Option<String>)> =
+            sqlx::query_as("SELECT id, idempotency_key, embedding::text FROM work_items")
+                .fetch_all(&self.pool)
+                .await?;
+        let mut extra: HashMap<Uuid, (Option<String>, Option<String>)> = extra
+            .into_iter()
+            .map(|(id, key, embedding)| (id, (key, embedding)))
+            .collect();
+        let work_items = rows
+            .into_iter()
+            .map(|r| {
+                let item = r.try_into_work_item()?;
+                let (idempotency_key, embedding) = extra.remove(&item.id.0).unwrap_or_default();
+                // pgvector's text form is a JSON array
+                let embedding = embedding
+                    .map(|v| serde_json::from_str(&v))
+                    .transpose()
+                    .map_err(|e| Error::Other(format!("embedding of {}: {e}", item.id)))?;
+                Ok(SnapshotItem {
+                    item,
+                    idempotency_key,
+                    embedding,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let attempts = sqlx::query_as(
+            "SELECT work_id, attempt_no, started_at, ended_at, outcome, error
+             FROM work_attempts ORDER BY work_id, attempt_no",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        let logs = sqlx::query_as(
+            "SELECT id, work_id, level, message, created_at FROM work_logs ORDER BY id",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        let events = sqlx::query_as(
+            "SELECT seq, work_id, kind, data, created_at FROM work_events ORDER BY seq",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let snapshot = Snapshot {
+            version: SNAPSHOT_VERSION,
+            exported_at: self.clock.now(),
+            work_items,
+            attempts,
+            logs,
+            events,
+        };
+        serde_json::to_writer_pretty(writer, &snapshot)
+            .map_err(|e| Error::Other(format!("write snapshot: {e}")))?;
+        Ok(())
+    }
+
+    /// Restore a document written by [`export_json`](Self::export_json).
+    ///
+    /// The database must hold no work items and no events. Ids, states,
+    /// and timestamps are restored exactly, as are log ids and event `seq`
+    /// numbers; their sequences then continue past the restored values. Items that were `Queued` get a fresh pgmq message so
+    /// they are claimable again; items that were in flight (`Claimed`,
+    /// `Running`) are restored as-is with no message.
+    pub async fn import_json(&self, reader: impl Read) -> Result<()> {
+        let snapshot: Snapshot = serde_json::from_reader(reader)
+            .map_err(|e| Error::Other(format!("read snapshot: {e}")))?;
+        if !(1..=SNAPSHOT_VERSION).contains(&snapshot.version) {
+            return Err(Error::Other(format!(
+                "unsupported snapshot version {} (expected {SNAPSHOT_VERSION})",
+                snapshot.version
+            )));
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        let existing: (i64,) = sqlx::query_as("SELECT count(*) FROM work_items")
+            .fetch_one(&mut *tx)
+            .await?;
+        if existing.0 > 0 {
+            return Err(Error::InvalidState(format!(
+                "import requires an empty database, found {} work items",
+                existing.0
+            )));
+        }
+        // Events outlive purged items, so check them on their own
+        let events: (i64,) = sqlx::query_as("SELECT count(*) FROM work_events")
+            .fetch_one(&mut *tx)
+            .await?;
+        if events.0 > 0 {
+            return Err(Error::InvalidState(format!(
+                "import requires an empty database, found {} events",
+                events.0
+            )));
+        }
+
+        // Rows first, links second: merged_into and parent_id reference
+        // other rows, which may appear later in the document.
+        for SnapshotItem {
+            item,
+            idempotency_key,
+            embedding,
+        } in &snapshot.work_items
+        {
+            let outcome = item.outcome.as_ref();
+            sqlx::query(
+                "INSERT INTO work_items (id, queue_name, faculty, skill, dedup_key, source, trigger_info, params, priority, state, attempts, max_attempts, outcome_data, outcome_error, outcome_ms, trace_context, deadline, created_at, updated_at, resolved_at, dedup_scope, expires_at, idempotency_key, embedding)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24::vector)",
+            )
+            .bind(item.id.0)
+            .bind(&item.queue)
+            .bind(&item.faculty)
+            .bind(&item.skill)
+            .bind(&item.dedup_key)
+            .bind(&item.provenance.source)
+            .bind(&item.provenance.trigger)
+            .bind(&item.params)
+            .bind(item.priority)
+            .bind(item.state.to_string())
+            .bind(item.attempts as i32)
+            .bind(item.max_attempts.map(|n| n as i32))
+            .bind(outcome.and_then(|o| o.data.as_ref()))
+            .bind(outcome.and_then(|o| o.error.as_deref()))
+            .bind(outcome.map(|o| o.duration_ms as i64))
+            .bind(item.trace_context.as_ref().map(|cx| serde_json::json!(cx)))
+            .bind(item.deadline)
+            .bind(item.created_at)
+            .bind(item.updated_at)
+            .bind(item.resolved_at)
+            .bind(&item.dedup_scope)
+            .bind(item.expires_at)
+            .bind(idempotency_key)
+            .bind(embedding.as_deref().map(format_vector))
+            .execute(&mut *tx)
+            .await?;
+            insert_tags(&mut tx, item.id.0, &item.tags).await?;
+        }
+
+        for SnapshotItem { item, .. } in &snapshot.work_items {
+            if item.merged_into.is_none() && item.parent_id.is_none() {
+                continue;
+            }
+            sqlx::query("UPDATE work_items SET merged_into = $1, parent_id = $2 WHERE id = $3")
+                .bind(item.merged_into.map(|id| id.0))
+                .bind(item.parent_id.map(|id| id.0))
+                .bind(item.id.0)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        self.import_history(&mut tx, &snapshot).await?;
+
+        for item in snapshot
+            .work_items
+            .iter()
+            .map(|s| &s.item)
+            .filter(|item| item.state == State::Queued)
+        {
+            let payload =
+                WorkPayload::new(item.id, &item.faculty, item.priority, item.params.clone());
+            let msg_id: (i64,) = sqlx::query_as("SELECT pgmq.send($1, $2, $3)")
+                .bind(&item.queue)
+                .bind(sqlx::types::Json(&payload))
+                .bind(0i32)
+                .fetch_one(&mut *tx)
+                .await?;
+            sqlx::query("UPDATE work_items SET pgmq_msg_id = $1 WHERE id = $2")
+                .bind(msg_id.0)
+                .bind(item.id.0)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Restore attempts, logs, and events with their original keys, then
+    /// move the id sequences past them.
+    async fn import_history(&self, tx: &mut sqlx::PgConnection, snapshot: &Snapshot) -> Result<()> {
+        let attempts = &snapshot.attempts;
+        sqlx::query(
+            "INSERT INTO work_attempts (work_id, attempt_no, started_at, ended_at, outcome, error)
+             SELECT * FROM unnest($1::uuid[], $2::int4[], $3::timestamptz[], $4::timestamptz[], $5::text[], $6::text[])",
+        )
+        .bind(attempts.iter().map(|a| a.work_id).collect::<Vec<_>>())
+        .bind(attempts.iter().map(|a| a.attempt_no).collect::<Vec<_>>())
+        .bind(attempts.iter().map(|a| a.started_at).collect::<Vec<_>>())
+        .bind(attempts.iter().map(|a| a.ended_at).collect::<Vec<_>>())
+        .bind(attempts.iter().map(|a| a.outcome.clone()).collect::<Vec<_>>())
+        .bind(attempts.iter().map(|a| a.error.clone()).collect::<Vec<_>>())
+        .execute(&mut *tx)
+        .await?;
+
+        let logs = &snapshot.logs;
+        sqlx::query(
+            "INSERT INTO work_logs (id, work_id, level, message, created_at)
+             SELECT * FROM unnest($1::int8[], $2::uuid[], $3::text[], $4::text[], $5::timestamptz[])",
+        )
+        .bind(logs.iter().map(|l| l.id).collect::<Vec<_>>())
+        .bind(logs.iter().map(|l| l.work_id).collect::<Vec<_>>())
+        .bind(logs.iter().map(|l| l.level.clone()).collect::<Vec<_>>())
+        .bind(logs.iter().map(|l| l.message.clone()).collect::<Vec<_>>())
+        .bind(logs.iter().map(|l| l.created_at).collect::<Vec<_>>())
+        .execute(&mut *tx)
+        .await?;
+
+        let events = &snapshot.events;
+        sqlx::query(
+            "INSERT INTO work_events (seq, work_id, kind, data, created_at)
+             SELECT * FROM unnest($1::int8[], $2::uuid[], $3::text[], $4::jsonb[], $5::timestamptz[])",
+        )
+        .bind(events.iter().map(|e| e.seq).collect::<Vec<_>>())
+        .bind(events.iter().map(|e| e.work_id).collect::<Vec<_>>())
+        .bind(events.iter().map(|e| e.kind.clone()).collect::<Vec<_>>())
+        .bind(events.iter().map(|e| e.data.clone()).collect::<Vec<_>>())
+        .bind(events.iter().map(|e| e.created_at).collect::<Vec<_>>())
+        .execute(&mut *tx)
+        .await?;
+
+        // setval is strict, so an empty table leaves its sequence alone
+        sqlx::query(
+            "SELECT setval(pg_get_serial_sequence('work_logs', 'id'), (SELECT max(id) FROM work_logs)),
+                    setval(pg_get_serial_sequence('work_events', 'seq'), (SELECT max(seq) FROM work_events))",
+        )
+        .execute(&mut *tx)
+        .await?;
+        Ok(())
+    }
+}
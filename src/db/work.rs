@@ -1,9 +1,13 @@
 //! Work item operations: submit with dedup, state tracking, provenance.
 
+use super::pgmq::{WorkPayload, poison, priority_tiers, read_condition};
 use crate::error::{Error, Result};
+use crate::faculty::{FacultyMeta, FacultyRegistry};
+use crate::memory::store::format_vector;
 use crate::model::work::*;
 use crate::telemetry::metrics;
 use opentelemetry::KeyValue;
+use std::collections::HashMap;
 use uuid::Uuid;
 
 /// Result of submitting work.
@@ -16,165 +20,1054 @@ pub enum SubmitResult {
         new_id: WorkId,
         canonical_id: WorkId,
     },
+    /// A submission with the same idempotency key was already made within
+    /// the window; nothing new was created.
+    AlreadyExists { id: WorkId },
+}
+
+/// Position in a `created_at DESC, id DESC` listing: the last item of the
+/// previous page. Renders as `<rfc3339>/<uuid>` for use on the command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorkCursor {
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub id: WorkId,
+}
+
+impl std::fmt::Display for WorkCursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}/{}",
+            self.created_at
+                .to_rfc3339_opts(chrono::SecondsFormat::Micros, true),
+            self.id.0
+        )
+    }
+}
+
+impl std::str::FromStr for WorkCursor {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let bad = || Error::Other(format!("bad work cursor: {s}"));
+        let (ts, id) = s.rsplit_once('/').ok_or_else(bad)?;
+        Ok(Self {
+            created_at: chrono::DateTime::parse_from_rfc3339(ts)
+                .map_err(|_| bad())?
+                .with_timezone(&chrono::Utc),
+            id: WorkId(Uuid::parse_str(id).map_err(|_| bad())?),
+        })
+    }
+}
+
+/// One page of work items, newest first.
+#[derive(Debug)]
+pub struct WorkPage {
+    pub items: Vec<WorkItem>,
+    /// Cursor for the following page; None on the last page.
+    pub next: Option<WorkCursor>,
+}
+
+/// Column a [`WorkItemFilter`] sorts by. Ties break on `id`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WorkOrder {
+    #[default]
+    CreatedAt,
+    Priority,
+}
+
+/// AND-combined conditions for [`Db::query_work_items`]. Empty sets match
+/// anything; results are newest first unless reordered.
+#[derive(Debug, Clone)]
+pub struct WorkItemFilter {
+    states: Vec<State>,
+    faculties: Vec<String>,
+    created_since: Option<chrono::DateTime<chrono::Utc>>,
+    min_priority: Option<i32>,
+    order: WorkOrder,
+    ascending: bool,
+    limit: i64,
+}
+
+impl Default for WorkItemFilter {
+    fn default() -> Self {
+        Self {
+            states: Vec::new(),
+            faculties: Vec::new(),
+            created_since: None,
+            min_priority: None,
+            order: WorkOrder::CreatedAt,
+            ascending: false,
+            limit: 100,
+        }
+    }
+}
+
+impl WorkItemFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Match items in `state`; repeat to match any of several.
+    pub fn state(mut self, state: State) -> Self {
+        self.states.push(state);
+        self
+    }
+
+    /// Match items for `faculty`; repeat to match any of several.
+    pub fn faculty(mut self, faculty: impl Into<String>) -> Self {
+        self.faculties.push(faculty.into());
+        self
+    }
+
+    pub fn created_since(mut self, since: chrono::DateTime<chrono::Utc>) -> Self {
+        self.created_since = Some(since);
+        self
+    }
+
+    pub fn min_priority(mut self, priority: i32) -> Self {
+        self.min_priority = Some(priority);
+        self
+    }
+
+    /// Sort by `order`, descending unless [`ascending`](Self::ascending).
+    pub fn order_by(mut self, order: WorkOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    pub fn ascending(mut self) -> Self {
+        self.ascending = true;
+        self
+    }
+
+    pub fn limit(mut self, limit: i64) -> Self {
+        self.limit = limit;
+        self
+    }
+}
+
+/// A claimed work item bundled with everything needed to start a focus.
+#[derive(Debug, Clone)]
+pub struct WorkContext {
+    /// The work item, in `Claimed` state.
+    pub item: WorkItem,
+    /// The faculty resolved from the item's `faculty` field.
+    pub faculty: FacultyMeta,
+    /// The pgmq message backing this claim; archive it on completion.
+    pub msg_id: i64,
+    /// Hook environment for this item (see [`crate::engine::work_env`]),
+    /// plus `ANIMUS_WORKER_ID`.
+    pub env: Vec<(String, String)>,
+}
+
+/// Most ids bound into a single `get_many` query; larger inputs are chunked.
+const GET_MANY_CHUNK: usize = 1000;
+
+/// Columns selected for every `WorkItemRow` read.
+pub(super) const WORK_ITEM_COLUMNS: &str = "id, queue_name, faculty, skill, dedup_key, dedup_scope, source, trigger_info, params, priority, state, merged_into, parent_id, attempts, max_attempts, created_at, updated_at, resolved_at, outcome_data, outcome_error, outcome_ms, trace_context, deadline, expires_at, ARRAY(SELECT tag FROM work_item_tags WHERE work_id = work_items.id ORDER BY tag) AS tags";
+
+/// Attach tags to a work item inside the caller's transaction.
+pub(super) async fn insert_tags(
+    tx: &mut sqlx::PgConnection,
+    id: Uuid,
+    tags: &[String],
+) -> Result<()> {
+    if tags.is_empty() {
+        return Ok(());
+    }
+    sqlx::query(
+        "INSERT INTO work_item_tags (work_id, tag)
+         SELECT $1, unnest($2::text[])
+         ON CONFLICT DO NOTHING",
+    )
+    .bind(id)
+    .bind(tags)
+    .execute(tx)
+    .await?;
+    Ok(())
+}
+
+/// Send a fresh queue message for `id`, for retries whose original
+/// message is gone.
+async fn resend(
+    tx: &mut sqlx::PgConnection,
+    id: WorkId,
+    queue: &str,
+    delay: std::time::Duration,
+) -> Result<()> {
+    let (faculty, priority, params): (String, i32, serde_json::Value) =
+        sqlx::query_as("SELECT faculty, priority, params FROM work_items WHERE id = $1")
+            .bind(id.0)
+            .fetch_one(&mut *tx)
+            .await?;
+    let payload = WorkPayload::new(id, faculty, priority, params);
+    let (msg_id,): (i64,) = sqlx::query_as("SELECT pgmq.send($1, $2, $3)")
+        .bind(queue)
+        .bind(sqlx::types::Json(&payload))
+        .bind(delay.as_secs() as i32)
+        .fetch_one(&mut *tx)
+        .await?;
+    sqlx::query("UPDATE work_items SET pgmq_msg_id = $1 WHERE id = $2")
+        .bind(msg_id)
+        .bind(id.0)
+        .execute(&mut *tx)
+        .await?;
+    Ok(())
+}
+
+/// Make `id`'s queue message visible now. If pgmq no longer holds
+/// `msg_id` (e.g. it was archived), a fresh message is sent instead.
+async fn make_visible(
+    tx: &mut sqlx::PgConnection,
+    id: WorkId,
+    queue: &str,
+    msg_id: Option<i64>,
+) -> Result<()> {
+    let moved: Option<(i64,)> = match msg_id {
+        Some(msg_id) => {
+            sqlx::query_as("SELECT msg_id FROM pgmq.set_vt($1, $2, 0)")
+                .bind(queue)
+                .bind(msg_id)
+                .fetch_optional(&mut *tx)
+                .await?
+        }
+        None => None,
+    };
+    if moved.is_none() {
+        resend(tx, id, queue, std::time::Duration::ZERO).await?;
+    }
+    Ok(())
+}
+
+/// Dead-letter items past their `expires_at` at `now` (just `only`, if
+/// given), archiving their queue messages. Returns each expired item with
+/// the state it left; the caller commits and records the transitions.
+async fn expire_due(
+    conn: &mut sqlx::PgConnection,
+    now: chrono::DateTime<chrono::Utc>,
+    only: Option<WorkId>,
+) -> Result<Vec<(Uuid, State)>> {
+    // SKIP LOCKED: items being changed right now are caught next time
+    let due: Vec<(Uuid, String, String, Option<i64>)> = sqlx::query_as(
+        "SELECT id, state, queue_name, pgmq_msg_id FROM work_items
+         WHERE expires_at <= $1 AND state IN ('queued', 'failed')
+         AND ($2::uuid IS NULL OR id = $2)
+         FOR UPDATE SKIP LOCKED",
+    )
+    .bind(now)
+    .bind(only.map(|id| id.0))
+    .fetch_all(&mut *conn)
+    .await?;
+    if due.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut expired = Vec::with_capacity(due.len());
+    for (id, state, ..) in &due {
+        let from: State = state.parse()?;
+        validate_transition(WorkId(*id), from, State::Dead)?;
+        expired.push((*id, from));
+    }
+    let ids: Vec<Uuid> = expired.iter().map(|(id, _)| *id).collect();
+    sqlx::query(
+        "UPDATE work_items SET state = 'dead', updated_at = $2, resolved_at = $2,
+            outcome_error = 'ttl expired'
+         WHERE id = ANY($1)",
+    )
+    .bind(&ids)
+    .bind(now)
+    .execute(&mut *conn)
+    .await?;
+    record_events(&mut *conn, &ids, &EventKind::Expired, now).await?;
+    for ((id, _, queue, msg_id), &(_, from)) in due.iter().zip(&expired) {
+        let state_changed = EventKind::StateChanged {
+            from,
+            to: State::Dead,
+        };
+        record_events(&mut *conn, &[*id], &state_changed, now).await?;
+        if let Some(msg_id) = msg_id {
+            sqlx::query("SELECT pgmq.archive($1, $2)")
+                .bind(queue)
+                .bind(msg_id)
+                .execute(&mut *conn)
+                .await?;
+        }
+    }
+    Ok(expired)
+}
+
+fn record_expired(expired: &[(Uuid, State)]) {
+    for &(_, from) in expired {
+        record_transition(from, State::Dead);
+    }
+    if !expired.is_empty() {
+        tracing::info!(count = expired.len(), "expired work past its ttl");
+    }
+}
+
+/// Move `id` from `from` to `to`, stamping `resolved_at` for terminal
+/// states. Entering Running counts an attempt and opens its history row;
+/// leaving Running closes it. The change is recorded in the event log.
+/// Returns the number of rows updated: 0 if the
+/// item wasn't in `from`.
+async fn apply_transition<'e>(
+    conn: impl sqlx::PgExecutor<'e>,
+    id: WorkId,
+    from: State,
+    to: State,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Result<u64> {
+    let resolved_at = if to.is_terminal() { Some(now) } else { None };
+    let attempts_increment = if to == State::Running { 1 } else { 0 };
+
+    let (moved,): (i64,) = sqlx::query_as(
+        "WITH moved AS (
+             UPDATE work_items SET state = $1, updated_at = $2, resolved_at = COALESCE($3, resolved_at), attempts = attempts + $4
+             WHERE id = $5 AND state = $6
+             RETURNING id
+         ),
+         started AS (
+             INSERT INTO work_attempts (work_id, attempt_no, started_at)
+             SELECT id, COALESCE((SELECT max(attempt_no) FROM work_attempts WHERE work_id = moved.id), 0) + 1, $2
+             FROM moved WHERE $1 = 'running'
+         ),
+         ended AS (
+             UPDATE work_attempts SET ended_at = $2, outcome = $1
+             WHERE work_id IN (SELECT id FROM moved) AND $6 = 'running' AND ended_at IS NULL
+         ),
+         logged AS (
+             INSERT INTO work_events (work_id, kind, data, created_at)
+             SELECT id, 'state_changed', $7, $2 FROM moved
+         )
+         SELECT count(*) FROM moved",
+    )
+    .bind(to.to_string())
+    .bind(now)
+    .bind(resolved_at)
+    .bind(attempts_increment)
+    .bind(id.0)
+    .bind(from.to_string())
+    .bind(event_data(&EventKind::StateChanged { from, to }))
+    .fetch_one(conn)
+    .await?;
+    Ok(moved as u64)
+}
+
+/// Append `event` to the event log once for each of `ids`. Callers pass
+/// their transaction, so the log commits or rolls back with the change.
+async fn record_events<'e>(
+    conn: impl sqlx::PgExecutor<'e>,
+    ids: &[Uuid],
+    event: &EventKind,
+    at: chrono::DateTime<chrono::Utc>,
+) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO work_events (work_id, kind, data, created_at)
+         SELECT id, $2, $3, $4 FROM unnest($1::uuid[]) AS id",
+    )
+    .bind(ids)
+    .bind(event.name())
+    .bind(event_data(event))
+    .bind(at)
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+fn event_data(event: &EventKind) -> serde_json::Value {
+    serde_json::to_value(event).expect("event kinds serialize to JSON")
+}
+
+type EventRow = (i64, Uuid, serde_json::Value, chrono::DateTime<chrono::Utc>);
+
+fn into_event((seq, work_id, data, created_at): EventRow) -> Result<Event> {
+    Ok(Event {
+        seq,
+        work_id: WorkId(work_id),
+        kind: serde_json::from_value(data)
+            .map_err(|e| Error::InvalidState(format!("event {seq}: {e}")))?,
+        created_at,
+    })
+}
+
+fn record_transition(from: State, to: State) {
+    metrics::work_state_transitions().add(
+        1,
+        &[
+            KeyValue::new("from", from.to_string()),
+            KeyValue::new("to", to.to_string()),
+        ],
+    );
+}
+
+/// Running → Failed with the error recorded, closing the open attempt.
+/// Returns the number of items updated: 0 if `id` wasn't running.
+async fn mark_failed<'e>(
+    conn: impl sqlx::PgExecutor<'e>,
+    id: WorkId,
+    error: &str,
+    data: Option<&serde_json::Value>,
+    duration_ms: u64,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Result<u64> {
+    Ok(sqlx::query(
+        "WITH ended AS (
+             UPDATE work_attempts SET ended_at = $1, outcome = 'failed', error = $2
+             WHERE work_id = $4 AND ended_at IS NULL
+             AND EXISTS (SELECT 1 FROM work_items WHERE id = $4 AND state = 'running')
+         ),
+         logged AS (
+             INSERT INTO work_events (work_id, kind, data, created_at)
+             SELECT $4, 'state_changed', $6, $1
+             WHERE EXISTS (SELECT 1 FROM work_items WHERE id = $4 AND state = 'running')
+         )
+         UPDATE work_items SET state = 'failed', updated_at = $1, outcome_error = $2, outcome_ms = $3, outcome_data = $5
+         WHERE id = $4 AND state = 'running'",
+    )
+    .bind(now)
+    .bind(error)
+    .bind(duration_ms as i64)
+    .bind(id.0)
+    .bind(data)
+    .bind(event_data(&EventKind::StateChanged {
+        from: State::Running,
+        to: State::Failed,
+    }))
+    .execute(conn)
+    .await?
+    .rows_affected())
+}
+
+/// Metrics for a committed Running → Failed.
+fn record_failed(duration_ms: u64) {
+    record_transition(State::Running, State::Failed);
+    metrics::operation_duration_ms().record(
+        duration_ms as f64,
+        &[KeyValue::new("operation", "work.execute")],
+    );
+}
+
+/// State, queue, message id, attempts, max attempts, and outcome error of
+/// an item being settled after a failure.
+type SettleRow = (
+    String,
+    String,
+    Option<i64>,
+    i32,
+    Option<i32>,
+    Option<String>,
+);
+
+/// When `new` expires, if it has a TTL.
+fn expiry(
+    new: &NewWorkItem,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Result<Option<chrono::DateTime<chrono::Utc>>> {
+    new.ttl
+        .map(|ttl| {
+            chrono::Duration::from_std(ttl)
+                .map(|ttl| now + ttl)
+                .map_err(|e| Error::InvalidState(format!("invalid ttl: {e}")))
+        })
+        .transpose()
 }
 
 /// Validate a state transition, returning an error if disallowed.
-fn validate_transition(from: State, to: State) -> Result<()> {
+fn validate_transition(id: WorkId, from: State, to: State) -> Result<()> {
     if from.can_transition_to(to) {
         Ok(())
     } else {
         Err(Error::InvalidTransition {
             from: from.to_string(),
             to: to.to_string(),
+            work_id: Some(id),
         })
     }
 }
 
 impl super::Db {
-    /// Submit new work. Checks structural dedup, sends to pgmq queue.
+    /// Submit new work. Validates it, checks structural dedup, sends to the
+    /// item's pgmq queue, and notifies `{queue}_ready`.
     pub async fn submit_work(&self, new: NewWorkItem) -> Result<SubmitResult> {
+        self.validate_submission(&new)?;
+
+        let mut tx = self.pool.begin().await?;
+        let (result, label) = self.submit_in(&mut tx, &new).await?;
+        tx.commit().await?;
+
+        metrics::work_submitted().add(
+            1,
+            &[
+                KeyValue::new("faculty", new.faculty),
+                KeyValue::new("result", label),
+            ],
+        );
+        Ok(result)
+    }
+
+    /// Report what [`submit_work`](Self::submit_work) would do with `new`
+    /// — create, merge, or match an idempotency key — without changing
+    /// anything. The submission runs in a transaction that is always
+    /// rolled back, so a `Created` item's id is never persisted.
+    ///
+    /// Locks taken by the dedup checks are held until the rollback, so this
+    /// briefly contends with real submissions of the same key.
+    pub async fn submit_dry_run(&self, new: &NewWorkItem) -> Result<SubmitResult> {
+        self.validate_submission(new)?;
+
+        let mut tx = self.pool.begin().await?;
+        let (result, _) = self.submit_in(&mut tx, new).await?;
+        tx.rollback().await?;
+        Ok(result)
+    }
+
+    /// Submit many items at once, for high-volume work that needs no
+    /// dedup. All of them are inserted and queued in one transaction with
+    /// a handful of statements, and each queue is notified once. Returns
+    /// the new ids in submission order.
+    ///
+    /// Items carrying a dedup or idempotency key — or an embedding, when
+    /// semantic dedup is on — are refused, since this path never looks for
+    /// duplicates; submit those through [`submit_work`](Self::submit_work).
+    /// One invalid item fails the whole batch.
+    pub async fn submit_work_batch(&self, items: Vec<NewWorkItem>) -> Result<Vec<WorkId>> {
+        if items.is_empty() {
+            return Ok(Vec::new());
+        }
+        let now = self.clock.now();
+
+        let mut ids = Vec::with_capacity(items.len());
+        let mut queues = Vec::with_capacity(items.len());
+        let mut faculties = Vec::with_capacity(items.len());
+        let mut skills = Vec::with_capacity(items.len());
+        let mut sources = Vec::with_capacity(items.len());
+        let mut triggers = Vec::with_capacity(items.len());
+        let mut params = Vec::with_capacity(items.len());
+        let mut priorities = Vec::with_capacity(items.len());
+        let mut parents = Vec::with_capacity(items.len());
+        let mut max_attempts = Vec::with_capacity(items.len());
+        let mut trace_contexts = Vec::with_capacity(items.len());
+        let mut deadlines = Vec::with_capacity(items.len());
+        let mut embeddings = Vec::with_capacity(items.len());
+        let mut expiries = Vec::with_capacity(items.len());
+        let mut payloads = Vec::with_capacity(items.len());
+        let mut submitted = Vec::with_capacity(items.len());
+        let (mut tag_ids, mut tags) = (Vec::new(), Vec::new());
+        for (i, new) in items.iter().enumerate() {
+            self.validate_submission(new)?;
+            if new.dedup_key.is_some() || new.idempotency_key.is_some() {
+                return Err(Error::InvalidState(format!(
+                    "batch item {i} has a dedup or idempotency key; submit it individually"
+                )));
+            }
+            if new.embedding.is_some() && self.semantic_dedup_threshold.is_some() {
+                return Err(Error::InvalidState(format!(
+                    "batch item {i} has an embedding and semantic dedup is on; submit it individually"
+                )));
+            }
+            let defaults = self
+                .work_type_defaults
+                .get(&new.faculty)
+                .copied()
+                .unwrap_or_default();
+            let priority = new.priority.unwrap_or(defaults.base_priority);
+            if let Some(ref scheme) = self.priority_scheme {
+                scheme.check(priority)?;
+            }
+
+            let id = Uuid::new_v4();
+            ids.push(id);
+            queues.push(new.queue.clone());
+            faculties.push(new.faculty.clone());
+            skills.push(new.skill.clone());
+            sources.push(new.provenance.source.clone());
+            triggers.push(new.provenance.trigger.clone());
+            params.push(new.params.clone());
+            priorities.push(priority);
+            parents.push(new.parent_id.map(|p| p.0));
+            max_attempts.push(new.max_attempts.or(defaults.max_attempts).map(|n| n as i32));
+            trace_contexts.push(new.trace_context.as_ref().map(|cx| serde_json::json!(cx)));
+            deadlines.push(new.deadline);
+            embeddings.push(new.embedding.as_deref().map(format_vector));
+            expiries.push(expiry(new, now)?);
+            payloads.push(serde_json::json!(WorkPayload::new(
+                WorkId(id),
+                &new.faculty,
+                priority,
+                new.params.clone()
+            )));
+            submitted.push(event_data(&EventKind::Submitted {
+                faculty: new.faculty.clone(),
+                source: new.provenance.source.clone(),
+            }));
+            for tag in &new.tags {
+                tag_ids.push(id);
+                tags.push(tag.clone());
+            }
+        }
+        validate_transition(WorkId(ids[0]), State::Created, State::Queued)?;
+
         let mut tx = self.pool.begin().await?;
+        for parent in items.iter().filter_map(|new| new.parent_id) {
+            self.check_ancestry(&mut tx, parent).await?;
+        }
+
+        // Send each item's message and insert it already queued, in one
+        // statement; the event log still records Created → Queued
+        sqlx::query(
+            "WITH batch AS (
+                 SELECT * FROM unnest($1::uuid[], $2::text[], $3::text[], $4::text[], $5::text[], $6::text[], $7::jsonb[], $8::int4[], $9::uuid[], $10::int4[], $11::jsonb[], $12::timestamptz[], $13::text[], $14::timestamptz[], $15::jsonb[])
+                     AS t(id, queue_name, faculty, skill, source, trigger_info, params, priority, parent_id, max_attempts, trace_context, deadline, embedding, expires_at, payload)
+             ),
+             sent AS (
+                 SELECT id, pgmq.send(queue_name, payload, 0) AS msg_id FROM batch
+             )
+             INSERT INTO work_items (id, queue_name, faculty, skill, source, trigger_info, params, priority, state, parent_id, max_attempts, trace_context, deadline, embedding, created_at, updated_at, expires_at, pgmq_msg_id)
+             SELECT b.id, b.queue_name, b.faculty, b.skill, b.source, b.trigger_info, b.params, b.priority, 'queued', b.parent_id, b.max_attempts, b.trace_context, b.deadline, b.embedding::vector, $16, $16, b.expires_at, s.msg_id
+             FROM batch b JOIN sent s USING (id)",
+        )
+        .bind(&ids)
+        .bind(&queues)
+        .bind(&faculties)
+        .bind(&skills)
+        .bind(&sources)
+        .bind(&triggers)
+        .bind(&params)
+        .bind(&priorities)
+        .bind(&parents)
+        .bind(&max_attempts)
+        .bind(&trace_contexts)
+        .bind(&deadlines)
+        .bind(&embeddings)
+        .bind(&expiries)
+        .bind(&payloads)
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+        if !tag_ids.is_empty() {
+            sqlx::query(
+                "INSERT INTO work_item_tags (work_id, tag)
+                 SELECT * FROM unnest($1::uuid[], $2::text[])
+                 ON CONFLICT DO NOTHING",
+            )
+            .bind(&tag_ids)
+            .bind(&tags)
+            .execute(&mut *tx)
+            .await?;
+        }
+        sqlx::query(
+            "INSERT INTO work_events (work_id, kind, data, created_at)
+             SELECT id, 'submitted', data, $3 FROM unnest($1::uuid[], $2::jsonb[]) AS t(id, data)",
+        )
+        .bind(&ids)
+        .bind(&submitted)
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+        record_events(
+            &mut *tx,
+            &ids,
+            &EventKind::StateChanged {
+                from: State::Created,
+                to: State::Queued,
+            },
+            now,
+        )
+        .await?;
+
+        // One notification per queue, naming the faculties it received
+        let mut ready: std::collections::BTreeMap<&str, std::collections::BTreeSet<&str>> =
+            Default::default();
+        for new in &items {
+            ready.entry(&new.queue).or_default().insert(&new.faculty);
+        }
+        let (ready_queues, ready_faculties): (Vec<&str>, Vec<String>) = ready
+            .into_iter()
+            .map(|(queue, faculties)| (queue, faculties.into_iter().collect::<Vec<_>>().join(",")))
+            .unzip();
+        sqlx::query(
+            "SELECT pg_notify(q || '_ready', f) FROM unnest($1::text[], $2::text[]) AS t(q, f)",
+        )
+        .bind(&ready_queues)
+        .bind(&ready_faculties)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        let mut counts: HashMap<&str, u64> = HashMap::new();
+        for new in &items {
+            *counts.entry(&new.faculty).or_default() += 1;
+        }
+        for (faculty, n) in counts {
+            metrics::work_submitted().add(
+                n,
+                &[
+                    KeyValue::new("faculty", faculty.to_string()),
+                    KeyValue::new("result", "ok"),
+                ],
+            );
+        }
+        Ok(ids.into_iter().map(WorkId).collect())
+    }
+
+    fn validate_submission(&self, new: &NewWorkItem) -> Result<()> {
+        if self.strict_params {
+            new.validate_strict()
+        } else {
+            new.validate()
+        }
+    }
+
+    /// Walk `parent`'s ancestors and refuse a child that would sit deeper
+    /// than `max_parent_depth`, or under an ancestry that loops (which
+    /// only imported or hand-edited rows can produce).
+    async fn check_ancestry(&self, tx: &mut sqlx::PgConnection, parent: WorkId) -> Result<()> {
+        // The walk stops one past the limit, so its cost is bounded too
+        let (depth, cycle): (Option<i32>, Option<bool>) = sqlx::query_as(
+            "WITH RECURSIVE ancestors AS (
+                 SELECT id, parent_id, 1 AS depth, ARRAY[id] AS path, false AS cycle
+                 FROM work_items WHERE id = $1
+               UNION ALL
+                 SELECT w.id, w.parent_id, a.depth + 1, a.path || w.id, w.id = ANY(a.path)
+                 FROM work_items w JOIN ancestors a ON w.id = a.parent_id
+                 WHERE NOT a.cycle AND a.depth < $2
+             )
+             SELECT max(depth), bool_or(cycle) FROM ancestors",
+        )
+        .bind(parent.0)
+        .bind(self.max_parent_depth as i32)
+        .fetch_one(&mut *tx)
+        .await?;
+        let Some(depth) = depth else {
+            return Err(Error::work_not_found(parent));
+        };
+        if cycle == Some(true) {
+            return Err(Error::InvalidState(format!(
+                "parent chain of {parent} loops"
+            )));
+        }
+        // The new item sits one below its parent
+        if depth as u32 >= self.max_parent_depth {
+            return Err(Error::InvalidState(format!(
+                "parent chain under {parent} would exceed max depth {}",
+                self.max_parent_depth
+            )));
+        }
+        Ok(())
+    }
+
+    /// The body of a submission, inside the caller's transaction. Returns
+    /// the result and its `work_submitted` metric label.
+    async fn submit_in(
+        &self,
+        tx: &mut sqlx::PgConnection,
+        new: &NewWorkItem,
+    ) -> Result<(SubmitResult, &'static str)> {
         let id = Uuid::new_v4();
-        let now = chrono::Utc::now();
+        let now = self.clock.now();
+        let trace_context = new.trace_context.as_ref().map(|cx| serde_json::json!(cx));
+        let embedding = new.embedding.as_deref().map(format_vector);
+        let defaults = self
+            .work_type_defaults
+            .get(&new.faculty)
+            .copied()
+            .unwrap_or_default();
+        let priority = new.priority.unwrap_or(defaults.base_priority);
+        if let Some(ref scheme) = self.priority_scheme {
+            scheme.check(priority)?;
+        }
+        let max_attempts = new.max_attempts.or(defaults.max_attempts);
+        let expires_at = expiry(new, now)?;
+        let submitted = EventKind::Submitted {
+            faculty: new.faculty.clone(),
+            source: new.provenance.source.clone(),
+        };
+
+        if let Some(ref key) = new.idempotency_key {
+            // Serialize submits sharing the key until this transaction ends,
+            // so concurrent retries can't both miss the lookup
+            sqlx::query("SELECT pg_advisory_xact_lock(hashtext($1))")
+                .bind(key)
+                .execute(&mut *tx)
+                .await?;
+            let window = chrono::Duration::from_std(self.idempotency_window)
+                .map_err(|e| Error::Config(format!("invalid idempotency window: {e}")))?;
+            let existing: Option<(Uuid,)> = sqlx::query_as(
+                "SELECT id FROM work_items
+                 WHERE idempotency_key = $1 AND created_at > $2
+                 ORDER BY created_at DESC
+                 LIMIT 1",
+            )
+            .bind(key)
+            .bind(now - window)
+            .fetch_optional(&mut *tx)
+            .await?;
+            if let Some((existing,)) = existing {
+                return Ok((
+                    SubmitResult::AlreadyExists {
+                        id: WorkId(existing),
+                    },
+                    "already_exists",
+                ));
+            }
+        }
+
+        if let Some(parent) = new.parent_id {
+            self.check_ancestry(&mut *tx, parent).await?;
+        }
+
+        // Dedup matches within the faculty, or across faculties sharing
+        // dedup_scope when one is set
+        let (scope_column, scope, scope_filter) = match new.dedup_scope {
+            Some(ref scope) => ("dedup_scope", scope, "dedup_scope IS NOT NULL"),
+            None => ("faculty", &new.faculty, "dedup_scope IS NULL"),
+        };
 
         if let Some(ref dedup_key) = new.dedup_key {
+            // Structural dedup matches on (scope, dedup_key), each scope
+            // kind backed by its own unique partial index
             // Attempt insert with ON CONFLICT for dedup-enabled items.
-            // The unique partial index on (faculty, dedup_key) prevents
-            // concurrent inserts with the same key for active items.
-            let inserted: Option<(Uuid,)> = sqlx::query_as(
-                "INSERT INTO work_items (id, queue_name, faculty, skill, dedup_key, source, trigger_info, params, priority, state, parent_id, max_attempts, created_at, updated_at)
-                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $13)
-                 ON CONFLICT (faculty, dedup_key) WHERE dedup_key IS NOT NULL AND state NOT IN ('completed', 'dead', 'merged')
+            // The unique index prevents concurrent inserts with the same
+            // key for active items.
+            let inserted: Option<(Uuid,)> = sqlx::query_as(&format!(
+                "INSERT INTO work_items (id, queue_name, faculty, skill, dedup_key, source, trigger_info, params, priority, state, parent_id, max_attempts, trace_context, deadline, embedding, created_at, updated_at, idempotency_key, dedup_scope, expires_at)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15::vector, $16, $16, $17, $18, $19)
+                 ON CONFLICT ({scope_column}, dedup_key) WHERE dedup_key IS NOT NULL AND {scope_filter} AND state NOT IN ('completed', 'dead', 'merged')
                  DO NOTHING
                  RETURNING id",
-            )
+            ))
             .bind(id)
-            .bind("work")
+            .bind(&new.queue)
             .bind(&new.faculty)
             .bind(&new.skill)
             .bind(dedup_key)
             .bind(&new.provenance.source)
             .bind(&new.provenance.trigger)
             .bind(&new.params)
-            .bind(new.priority)
+            .bind(priority)
             .bind("created")
             .bind(new.parent_id.map(|p| p.0))
-            .bind(new.max_attempts.map(|n| n as i32))
+            .bind(max_attempts.map(|n| n as i32))
+            .bind(trace_context.as_ref())
+            .bind(new.deadline)
+            .bind(embedding.as_deref())
             .bind(now)
+            .bind(&new.idempotency_key)
+            .bind(&new.dedup_scope)
+            .bind(expires_at)
             .fetch_optional(&mut *tx)
             .await?;
 
             if inserted.is_none() {
-                // Conflict: a duplicate exists. Find the canonical item.
-                let canonical: (Uuid,) = sqlx::query_as(
-                    "SELECT id FROM work_items
-                     WHERE faculty = $1 AND dedup_key = $2
+                // Conflict: a duplicate exists. Find the canonical item and
+                // lock it so it can't resolve before the merge commits.
+                let canonical: Option<(Uuid, String)> = sqlx::query_as(&format!(
+                    "SELECT id, state FROM work_items
+                     WHERE {scope_column} = $1 AND dedup_key = $2 AND {scope_filter}
                      AND state NOT IN ('completed', 'dead', 'merged')
-                     LIMIT 1",
-                )
-                .bind(&new.faculty)
+                     LIMIT 1
+                     FOR SHARE",
+                ))
+                .bind(scope)
                 .bind(dedup_key)
-                .fetch_one(&mut *tx)
+                .fetch_optional(&mut *tx)
                 .await?;
 
+                // Only the new submission is ever merged away. The canonical
+                // must still be active, whatever stage it has reached.
+                let canonical = match canonical {
+                    Some((canonical_id, state)) if canonical_id != id => {
+                        let state: State = state.parse()?;
+                        if state.is_terminal() {
+                            return Err(Error::InvalidState(format!(
+                                "dedup canonical {} is {state}",
+                                WorkId(canonical_id)
+                            )));
+                        }
+                        (canonical_id,)
+                    }
+                    _ => {
+                        return Err(Error::InvalidState(format!(
+                            "dedup canonical for ({scope}, {dedup_key}) resolved concurrently; resubmit"
+                        )));
+                    }
+                };
+
                 // Insert the new item as merged (dedup_key = NULL to avoid
                 // conflicting with the unique index).
-                validate_transition(State::Created, State::Merged)?;
+                validate_transition(WorkId(id), State::Created, State::Merged)?;
                 sqlx::query(
-                    "INSERT INTO work_items (id, queue_name, faculty, skill, dedup_key, source, trigger_info, params, priority, state, merged_into, parent_id, max_attempts, created_at, updated_at, resolved_at)
-                     VALUES ($1, $2, $3, $4, NULL, $5, $6, $7, $8, 'merged', $9, $10, $11, $12, $12, $12)",
+                    "INSERT INTO work_items (id, queue_name, faculty, skill, dedup_key, source, trigger_info, params, priority, state, merged_into, parent_id, max_attempts, trace_context, deadline, embedding, created_at, updated_at, resolved_at, idempotency_key, dedup_scope, expires_at)
+                     VALUES ($1, $2, $3, $4, NULL, $5, $6, $7, $8, 'merged', $9, $10, $11, $12, $13, $14::vector, $15, $15, $15, $16, $17, $18)",
                 )
                 .bind(id)
-                .bind("work")
+                .bind(&new.queue)
                 .bind(&new.faculty)
                 .bind(&new.skill)
                 .bind(&new.provenance.source)
                 .bind(&new.provenance.trigger)
                 .bind(&new.params)
-                .bind(new.priority)
+                .bind(priority)
                 .bind(canonical.0)
                 .bind(new.parent_id.map(|p| p.0))
-                .bind(new.max_attempts.map(|n| n as i32))
+                .bind(max_attempts.map(|n| n as i32))
+                .bind(trace_context.as_ref())
+                .bind(new.deadline)
+                .bind(embedding.as_deref())
                 .bind(now)
+                .bind(&new.idempotency_key)
+                .bind(&new.dedup_scope)
+                .bind(expires_at)
                 .execute(&mut *tx)
                 .await?;
-
-                tx.commit().await?;
-                metrics::work_submitted().add(
-                    1,
-                    &[
-                        KeyValue::new("faculty", new.faculty.clone()),
-                        KeyValue::new("result", "duplicate"),
-                    ],
-                );
-                return Ok(SubmitResult::Merged {
-                    new_id: WorkId(id),
-                    canonical_id: WorkId(canonical.0),
-                });
+                insert_tags(&mut *tx, id, &new.tags).await?;
+                record_events(&mut *tx, &[id], &submitted, now).await?;
+                record_events(
+                    &mut *tx,
+                    &[id],
+                    &EventKind::Merged {
+                        canonical: WorkId(canonical.0),
+                    },
+                    now,
+                )
+                .await?;
+                return Ok((
+                    SubmitResult::Merged {
+                        new_id: WorkId(id),
+                        canonical_id: WorkId(canonical.0),
+                    },
+                    "duplicate",
+                ));
             }
         } else {
             // No dedup key — straight insert, no conflict possible
             sqlx::query(
-                "INSERT INTO work_items (id, queue_name, faculty, skill, dedup_key, source, trigger_info, params, priority, state, parent_id, max_attempts, created_at, updated_at)
-                 VALUES ($1, $2, $3, $4, NULL, $5, $6, $7, $8, $9, $10, $11, $12, $12)",
+                "INSERT INTO work_items (id, queue_name, faculty, skill, dedup_key, source, trigger_info, params, priority, state, parent_id, max_attempts, trace_context, deadline, embedding, created_at, updated_at, idempotency_key, expires_at)
+                 VALUES ($1, $2, $3, $4, NULL, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14::vector, $15, $15, $16, $17)",
             )
             .bind(id)
-            .bind("work")
+            .bind(&new.queue)
             .bind(&new.faculty)
             .bind(&new.skill)
             .bind(&new.provenance.source)
             .bind(&new.provenance.trigger)
             .bind(&new.params)
-            .bind(new.priority)
+            .bind(priority)
             .bind("created")
             .bind(new.parent_id.map(|p| p.0))
-            .bind(new.max_attempts.map(|n| n as i32))
+            .bind(max_attempts.map(|n| n as i32))
+            .bind(trace_context.as_ref())
+            .bind(new.deadline)
+            .bind(embedding.as_deref())
             .bind(now)
+            .bind(&new.idempotency_key)
+            .bind(expires_at)
             .execute(&mut *tx)
             .await?;
         }
 
+        insert_tags(&mut *tx, id, &new.tags).await?;
+        record_events(&mut *tx, &[id], &submitted, now).await?;
+
+        // No structural duplicate. Look for a semantic one.
+        if let (Some(threshold), Some(embedding)) = (self.semantic_dedup_threshold, &embedding) {
+            let similar: Option<(Uuid,)> = sqlx::query_as(&format!(
+                "SELECT id FROM work_items
+                 WHERE {scope_column} = $1 AND {scope_filter} AND id <> $2
+                 AND embedding IS NOT NULL
+                 AND state NOT IN ('completed', 'dead', 'merged')
+                 AND 1 - (embedding <=> $3::vector) >= $4
+                 ORDER BY embedding <=> $3::vector
+                 LIMIT 1
+                 FOR SHARE",
+            ))
+            .bind(scope)
+            .bind(id)
+            .bind(embedding)
+            .bind(threshold)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+            if let Some((canonical_id,)) = similar {
+                validate_transition(WorkId(id), State::Created, State::Merged)?;
+                sqlx::query(
+                    "UPDATE work_items SET state = 'merged', merged_into = $1, dedup_key = NULL, updated_at = $2, resolved_at = $2
+                     WHERE id = $3",
+                )
+                .bind(canonical_id)
+                .bind(now)
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+                record_events(
+                    &mut *tx,
+                    &[id],
+                    &EventKind::Merged {
+                        canonical: WorkId(canonical_id),
+                    },
+                    now,
+                )
+                .await?;
+                return Ok((
+                    SubmitResult::Merged {
+                        new_id: WorkId(id),
+                        canonical_id: WorkId(canonical_id),
+                    },
+                    "semantic_duplicate",
+                ));
+            }
+        }
+
         // Inserted successfully — queue via pgmq
-        validate_transition(State::Created, State::Queued)?;
+        validate_transition(WorkId(id), State::Created, State::Queued)?;
 
-        let payload = serde_json::json!({
-            "work_item_id": id,
-            "params": new.params
-        });
+        let payload = WorkPayload::new(WorkId(id), &new.faculty, priority, new.params.clone());
         let msg_id: (i64,) = sqlx::query_as("SELECT pgmq.send($1, $2, $3)")
-            .bind("work")
-            .bind(&payload)
+            .bind(&new.queue)
+            .bind(sqlx::types::Json(&payload))
             .bind(0i32)
             .fetch_one(&mut *tx)
             .await?;
 
         // Update work item with pgmq msg ID and state
         sqlx::query(
-            "UPDATE work_items SET state = 'queued', pgmq_msg_id = $1, updated_at = now() WHERE id = $2",
+            "UPDATE work_items SET state = 'queued', pgmq_msg_id = $1, updated_at = $3 WHERE id = $2",
         )
         .bind(msg_id.0)
         .bind(id)
+        .bind(now)
         .execute(&mut *tx)
         .await?;
+        record_events(
+            &mut *tx,
+            &[id],
+            &EventKind::StateChanged {
+                from: State::Created,
+                to: State::Queued,
+            },
+            now,
+        )
+        .await?;
 
         // NOTIFY is transactional — only fires on commit
-        sqlx::query("SELECT pg_notify('work_ready', $1)")
+        sqlx::query("SELECT pg_notify($1 || '_ready', $2)")
+            .bind(&new.queue)
             .bind(&new.faculty)
             .execute(&mut *tx)
             .await?;
 
-        tx.commit().await?;
-
-        metrics::work_submitted().add(
-            1,
-            &[
-                KeyValue::new("faculty", new.faculty),
-                KeyValue::new("result", "ok"),
-            ],
-        );
-
-        let item = self.get_work_item(WorkId(id)).await?;
-        Ok(SubmitResult::Created(Box::new(item)))
+        let row: WorkItemRow = sqlx::query_as(&format!(
+            "SELECT {WORK_ITEM_COLUMNS}
+             FROM work_items WHERE id = $1",
+        ))
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+        Ok((
+            SubmitResult::Created(Box::new(row.try_into_work_item()?)),
+            "ok",
+        ))
     }
 
     /// List work items with optional filters.
@@ -184,120 +1077,907 @@ impl super::Db {
         faculty: Option<&str>,
         limit: i64,
     ) -> Result<Vec<WorkItem>> {
-        // Build query dynamically based on filters
-        let rows: Vec<WorkItemRow> = match (state, faculty) {
-            (Some(s), Some(wt)) => {
-                sqlx::query_as(
-                    "SELECT id, faculty, skill, dedup_key, source, trigger_info, params, priority, state, merged_into, parent_id, attempts, max_attempts, created_at, updated_at, resolved_at, outcome_data, outcome_error, outcome_ms
-                     FROM work_items WHERE state = $1 AND faculty = $2
-                     ORDER BY created_at DESC LIMIT $3",
-                )
-                .bind(s.to_string())
-                .bind(wt)
-                .bind(limit)
-                .fetch_all(&self.pool)
-                .await?
-            }
-            (Some(s), None) => {
-                sqlx::query_as(
-                    "SELECT id, faculty, skill, dedup_key, source, trigger_info, params, priority, state, merged_into, parent_id, attempts, max_attempts, created_at, updated_at, resolved_at, outcome_data, outcome_error, outcome_ms
-                     FROM work_items WHERE state = $1
-                     ORDER BY created_at DESC LIMIT $2",
-                )
-                .bind(s.to_string())
-                .bind(limit)
-                .fetch_all(&self.pool)
-                .await?
-            }
-            (None, Some(wt)) => {
-                sqlx::query_as(
-                    "SELECT id, faculty, skill, dedup_key, source, trigger_info, params, priority, state, merged_into, parent_id, attempts, max_attempts, created_at, updated_at, resolved_at, outcome_data, outcome_error, outcome_ms
-                     FROM work_items WHERE faculty = $1
-                     ORDER BY created_at DESC LIMIT $2",
-                )
-                .bind(wt)
-                .bind(limit)
-                .fetch_all(&self.pool)
-                .await?
-            }
-            (None, None) => {
-                sqlx::query_as(
-                    "SELECT id, faculty, skill, dedup_key, source, trigger_info, params, priority, state, merged_into, parent_id, attempts, max_attempts, created_at, updated_at, resolved_at, outcome_data, outcome_error, outcome_ms
-                     FROM work_items
-                     ORDER BY created_at DESC LIMIT $1",
-                )
-                .bind(limit)
-                .fetch_all(&self.pool)
-                .await?
-            }
+        let mut filter = WorkItemFilter::new().limit(limit);
+        if let Some(state) = state {
+            filter = filter.state(state);
+        }
+        if let Some(faculty) = faculty {
+            filter = filter.faculty(faculty);
+        }
+        self.query_work_items(&filter).await
+    }
+
+    /// List work items matching every condition in `filter`.
+    pub async fn query_work_items(&self, filter: &WorkItemFilter) -> Result<Vec<WorkItem>> {
+        let column = match filter.order {
+            WorkOrder::CreatedAt => "created_at",
+            WorkOrder::Priority => "priority",
         };
+        let direction = if filter.ascending { "ASC" } else { "DESC" };
+        let states: Vec<String> = filter.states.iter().map(State::to_string).collect();
+
+        let rows: Vec<WorkItemRow> = sqlx::query_as(&format!(
+            "SELECT {WORK_ITEM_COLUMNS}
+             FROM work_items
+             WHERE (cardinality($1::text[]) = 0 OR state = ANY($1))
+             AND (cardinality($2::text[]) = 0 OR faculty = ANY($2))
+             AND ($3::timestamptz IS NULL OR created_at >= $3)
+             AND ($4::int IS NULL OR priority >= $4)
+             ORDER BY {column} {direction}, id {direction}
+             LIMIT $5",
+        ))
+        .bind(&states)
+        .bind(&filter.faculties)
+        .bind(filter.created_since)
+        .bind(filter.min_priority)
+        .bind(filter.limit)
+        .fetch_all(&self.pool)
+        .await?;
 
         rows.into_iter().map(|r| r.try_into_work_item()).collect()
     }
 
-    /// Get a work item by ID.
-    pub async fn get_work_item(&self, id: WorkId) -> Result<WorkItem> {
-        let row: Option<WorkItemRow> = sqlx::query_as(
-            "SELECT id, faculty, skill, dedup_key, source, trigger_info, params, priority, state, merged_into, parent_id, attempts, max_attempts, created_at, updated_at, resolved_at, outcome_data, outcome_error, outcome_ms
-             FROM work_items WHERE id = $1",
-        )
-        .bind(id.0)
-        .fetch_optional(&self.pool)
+    /// List work items with optional filters, one keyset page at a time.
+    ///
+    /// Pass the previous page's `next` as `before` to continue. Ordering is
+    /// `created_at DESC, id DESC`, so pages stay stable as new work arrives.
+    pub async fn list_work_items_page(
+        &self,
+        state: Option<State>,
+        faculty: Option<&str>,
+        limit: i64,
+        before: Option<WorkCursor>,
+    ) -> Result<WorkPage> {
+        // Fetch one extra row to learn whether another page exists
+        let mut rows: Vec<WorkItemRow> = sqlx::query_as(&format!(
+            "SELECT {WORK_ITEM_COLUMNS}
+             FROM work_items
+             WHERE ($1::text IS NULL OR state = $1)
+             AND ($2::text IS NULL OR faculty = $2)
+             AND ($3::timestamptz IS NULL OR (created_at, id) < ($3, $4))
+             ORDER BY created_at DESC, id DESC
+             LIMIT $5",
+        ))
+        .bind(state.map(|s| s.to_string()))
+        .bind(faculty)
+        .bind(before.map(|c| c.created_at))
+        .bind(before.map(|c| c.id.0))
+        .bind(limit + 1)
+        .fetch_all(&self.pool)
         .await?;
 
-        row.ok_or_else(|| Error::NotFound(format!("work item {id}")))?
-            .try_into_work_item()
-    }
-
-    /// Transition a work item's state with optimistic concurrency.
-    pub async fn transition_state(&self, id: WorkId, from: State, to: State) -> Result<WorkItem> {
-        validate_transition(from, to)?;
+        let more = rows.len() as i64 > limit;
+        rows.truncate(limit.max(0) as usize);
+        let items = rows
+            .into_iter()
+            .map(|r| r.try_into_work_item())
+            .collect::<Result<Vec<_>>>()?;
+        let next = if more {
+            items.last().map(|item| WorkCursor {
+                created_at: item.created_at,
+                id: item.id,
+            })
+        } else {
+            None
+        };
+
+        Ok(WorkPage { items, next })
+    }
+
+    /// Read the next message from `queue`, claim its item
+    /// (Queued → Claimed), and resolve its faculty — one transaction, so an
+    /// out-of-process worker can start a focus immediately.
+    ///
+    /// Returns `None` if the queue holds nothing claimable. Messages that
+    /// can't be claimed are skipped: if the item names a faculty the
+    /// registry doesn't know, the message stays invisible until the
+    /// visibility timeout, as in the control plane; a message whose work
+    /// item no longer exists is archived; expired work is dead-lettered;
+    /// and an unparseable message is moved to the poison queue.
+    pub async fn claim_work_context(
+        &self,
+        queue: &str,
+        worker_id: &str,
+        registry: &FacultyRegistry,
+        vt_seconds: i32,
+    ) -> Result<Option<WorkContext>> {
+        self.claim_work_context_matching(queue, worker_id, registry, vt_seconds, &[])
+            .await
+    }
+
+    /// Like [`claim_work_context`](Self::claim_work_context), but only
+    /// claims work for the given faculties, so specialized workers can share
+    /// the queue. Higher-priority work is claimed first; within a priority,
+    /// faculties are tried in order, each oldest-first. An empty list
+    /// matches any faculty.
+    ///
+    /// Matching uses the `faculty` field of the queue message, so messages
+    /// sent before it was added are only claimed by unfiltered calls.
+    pub async fn claim_work_context_matching(
+        &self,
+        queue: &str,
+        worker_id: &str,
+        registry: &FacultyRegistry,
+        vt_seconds: i32,
+        faculties: &[String],
+    ) -> Result<Option<WorkContext>> {
+        let faculties: Vec<Option<&str>> = if faculties.is_empty() {
+            vec![None]
+        } else {
+            faculties.iter().map(|f| Some(f.as_str())).collect()
+        };
+        let tiers = priority_tiers(&mut *self.pool.acquire().await?, queue).await?;
+        let conditions = tiers.iter().flat_map(|&priority| {
+            faculties
+                .iter()
+                .map(move |&faculty| read_condition(faculty, priority))
+        });
+
+        for condition in conditions {
+            // Each skipped message is archived, poisoned, or hidden by its
+            // read, so this walks the matching messages at most once
+            loop {
+                let mut tx = self.pool.begin().await?;
+                let msg: Option<(i64, serde_json::Value)> =
+                    sqlx::query_as("SELECT msg_id, message FROM pgmq.read($1, $2, 1, $3)")
+                        .bind(queue)
+                        .bind(vt_seconds)
+                        .bind(&condition)
+                        .fetch_optional(&mut *tx)
+                        .await?;
+                let Some((msg_id, message)) = msg else {
+                    break;
+                };
+                if let Some(ctx) = self
+                    .claim_message(tx, queue, msg_id, message, worker_id, registry)
+                    .await?
+                {
+                    return Ok(Some(ctx));
+                }
+            }
+        }
+        Ok(None)
+    }
 
-        let now = chrono::Utc::now();
-        let resolved_at = if to.is_terminal() { Some(now) } else { None };
+    /// Claim the work item behind a message read in `tx`, committing `tx`.
+    /// Returns `None` if the message was skipped.
+    async fn claim_message(
+        &self,
+        mut tx: sqlx::Transaction<'_, sqlx::Postgres>,
+        queue: &str,
+        msg_id: i64,
+        message: serde_json::Value,
+        worker_id: &str,
+        registry: &FacultyRegistry,
+    ) -> Result<Option<WorkContext>> {
+        let work_id = match WorkPayload::parse(&message) {
+            Ok(payload) => payload.work_id(),
+            Err(e) => {
+                tracing::warn!(queue, msg_id, "poison message: {e}");
+                poison(&mut tx, queue, msg_id, &message, &e.to_string()).await?;
+                tx.commit().await?;
+                return Ok(None);
+            }
+        };
 
-        // Increment attempts when entering Running
-        let attempts_increment = if to == State::Running { 1 } else { 0 };
+        let faculty: Option<(String, Option<chrono::DateTime<chrono::Utc>>)> =
+            sqlx::query_as("SELECT faculty, expires_at FROM work_items WHERE id = $1")
+                .bind(work_id.0)
+                .fetch_optional(&mut *tx)
+                .await?;
+        let Some((faculty, expires_at)) = faculty else {
+            // The item is gone (e.g. purged); its message can never be
+            // claimed, so archive it rather than let it reappear.
+            sqlx::query("SELECT pgmq.archive($1, $2)")
+                .bind(queue)
+                .bind(msg_id)
+                .execute(&mut *tx)
+                .await?;
+            tx.commit().await?;
+            tracing::debug!(work_id = %work_id, "work item gone, archived its message");
+            return Ok(None);
+        };
+        if expires_at.is_some_and(|at| at <= self.clock.now()) {
+            // Never claimed: dead-letter it (archiving the message) instead
+            tracing::info!(work_id = %work_id, "work outlived its ttl, expiring");
+            let expired = expire_due(&mut tx, self.clock.now(), Some(work_id)).await?;
+            tx.commit().await?;
+            record_expired(&expired);
+            return Ok(None);
+        }
+        let Some(faculty) = registry.get(&faculty).cloned() else {
+            // Keep the read (the message stays invisible) but don't claim.
+            tx.commit().await?;
+            tracing::warn!(work_id = %work_id, %faculty, "no faculty registered, skipping");
+            return Ok(None);
+        };
 
+        validate_transition(work_id, State::Queued, State::Claimed)?;
+        let now = self.clock.now();
         let rows_affected = sqlx::query(
-            "UPDATE work_items SET state = $1, updated_at = $2, resolved_at = COALESCE($3, resolved_at), attempts = attempts + $4
-             WHERE id = $5 AND state = $6",
+            "UPDATE work_items SET state = 'claimed', updated_at = $2
+             WHERE id = $1 AND state = 'queued'",
         )
-        .bind(to.to_string())
+        .bind(work_id.0)
         .bind(now)
-        .bind(resolved_at)
-        .bind(attempts_increment)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        if rows_affected > 0 {
+            record_events(
+                &mut *tx,
+                &[work_id.0],
+                &EventKind::StateChanged {
+                    from: State::Queued,
+                    to: State::Claimed,
+                },
+                now,
+            )
+            .await?;
+        }
+
+        // Either way the read is committed: a stale message for an item
+        // already past Queued must not become visible again straight away.
+        tx.commit().await?;
+
+        if rows_affected == 0 {
+            tracing::debug!(work_id = %work_id, "work not queued, skipping");
+            return Ok(None);
+        }
+
+        metrics::work_state_transitions().add(
+            1,
+            &[
+                KeyValue::new("from", "queued"),
+                KeyValue::new("to", "claimed"),
+            ],
+        );
+
+        let item = self.get_work_item(work_id).await?;
+        let mut env = crate::engine::work_env(&item);
+        env.push(("ANIMUS_WORKER_ID".to_string(), worker_id.to_string()));
+
+        tracing::info!(work_id = %work_id, worker_id, faculty = %faculty.name, "work claimed");
+
+        Ok(Some(WorkContext {
+            item,
+            faculty,
+            msg_id,
+            env,
+        }))
+    }
+
+    /// Get a work item by ID.
+    pub async fn get_work_item(&self, id: WorkId) -> Result<WorkItem> {
+        let row: Option<WorkItemRow> = sqlx::query_as(&format!(
+            "SELECT {WORK_ITEM_COLUMNS}
+             FROM work_items WHERE id = $1",
+        ))
         .bind(id.0)
-        .bind(from.to_string())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.ok_or_else(|| Error::work_not_found(id))?
+            .try_into_work_item()
+    }
+
+    /// Get several work items in as few queries as possible. Items come back
+    /// in the order of `ids`; missing ids and repeats are skipped.
+    pub async fn get_many(&self, ids: &[WorkId]) -> Result<Vec<WorkItem>> {
+        let mut found = HashMap::with_capacity(ids.len());
+        for chunk in ids.chunks(GET_MANY_CHUNK) {
+            let uuids: Vec<Uuid> = chunk.iter().map(|id| id.0).collect();
+            let rows: Vec<WorkItemRow> = sqlx::query_as(&format!(
+                "SELECT {WORK_ITEM_COLUMNS}
+                 FROM work_items WHERE id = ANY($1)",
+            ))
+            .bind(&uuids)
+            .fetch_all(&self.pool)
+            .await?;
+            for row in rows {
+                let item = row.try_into_work_item()?;
+                found.insert(item.id, item);
+            }
+        }
+
+        Ok(ids.iter().filter_map(|id| found.remove(id)).collect())
+    }
+
+    /// Number of work items in each state. Every state is present, with
+    /// zero for states that have no items.
+    pub async fn count_by_state(&self) -> Result<HashMap<State, u64>> {
+        let rows: Vec<(String, i64)> =
+            sqlx::query_as("SELECT state, count(*) FROM work_items GROUP BY state")
+                .fetch_all(&self.pool)
+                .await?;
+
+        let mut counts: HashMap<State, u64> = State::ALL.iter().map(|s| (*s, 0)).collect();
+        for (state, n) in rows {
+            counts.insert(state.parse()?, n as u64);
+        }
+        Ok(counts)
+    }
+
+    /// Provenance of every submission merged into `canonical`, oldest first.
+    ///
+    /// Merged rows removed by [`Db::purge_completed`] no longer appear.
+    pub async fn get_merged_provenance(&self, canonical: WorkId) -> Result<Vec<ProvenanceEntry>> {
+        let rows: Vec<(Uuid, String, Option<String>, chrono::DateTime<chrono::Utc>)> =
+            sqlx::query_as(
+                "SELECT id, source, trigger_info, created_at
+             FROM work_items
+             WHERE merged_into = $1
+             ORDER BY created_at, id",
+            )
+            .bind(canonical.0)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(id, source, trigger, created_at)| ProvenanceEntry {
+                id: WorkId(id),
+                provenance: Provenance { source, trigger },
+                created_at,
+            })
+            .collect())
+    }
+
+    /// Add a line to `id`'s durable log. The line is also emitted as a
+    /// `tracing` event at the same level, tagged with `work_id`, so it
+    /// reaches the OTel log pipeline inside the current span.
+    pub async fn append_log(&self, id: WorkId, level: LogLevel, message: &str) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO work_logs (work_id, level, message, created_at)
+             VALUES ($1, $2, $3, $4)",
+        )
+        .bind(id.0)
+        .bind(level.to_string())
+        .bind(message)
+        .bind(self.clock.now())
+        .execute(&self.pool)
+        .await?;
+        match level {
+            LogLevel::Debug => tracing::debug!(work_id = %id, "{message}"),
+            LogLevel::Info => tracing::info!(work_id = %id, "{message}"),
+            LogLevel::Warn => tracing::warn!(work_id = %id, "{message}"),
+            LogLevel::Error => tracing::error!(work_id = %id, "{message}"),
+        }
+        Ok(())
+    }
+
+    /// `id`'s log, oldest first.
+    pub async fn get_logs(&self, id: WorkId) -> Result<Vec<LogEntry>> {
+        let rows: Vec<(i64, String, String, chrono::DateTime<chrono::Utc>)> = sqlx::query_as(
+            "SELECT id, level, message, created_at
+             FROM work_logs
+             WHERE work_id = $1
+             ORDER BY id",
+        )
+        .bind(id.0)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|(log_id, level, message, created_at)| {
+                Ok(LogEntry {
+                    id: log_id,
+                    work_id: id,
+                    level: level.parse()?,
+                    message,
+                    created_at,
+                })
+            })
+            .collect()
+    }
+
+    /// Append an event for `id` to the event log. Returns its sequence
+    /// number. The engine records its own lifecycle events; this is for
+    /// changes made outside it.
+    pub async fn record_event(&self, id: WorkId, event: &EventKind) -> Result<i64> {
+        let (seq,): (i64,) = sqlx::query_as(
+            "INSERT INTO work_events (work_id, kind, data, created_at)
+             VALUES ($1, $2, $3, $4)
+             RETURNING seq",
+        )
+        .bind(id.0)
+        .bind(event.name())
+        .bind(event_data(event))
+        .bind(self.clock.now())
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(seq)
+    }
+
+    /// Up to `limit` events with sequence numbers above `after`, oldest
+    /// first. Pass the last `seq` seen to continue; 0 starts at the
+    /// beginning.
+    ///
+    /// Sequence numbers are assigned at insert, so a transaction that
+    /// commits late can land behind a `seq` a reader has already passed.
+    pub async fn get_events_since(&self, after: i64, limit: i64) -> Result<Vec<Event>> {
+        let rows: Vec<EventRow> = sqlx::query_as(
+            "SELECT seq, work_id, data, created_at
+             FROM work_events
+             WHERE seq > $1
+             ORDER BY seq
+             LIMIT $2",
+        )
+        .bind(after)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(into_event).collect()
+    }
+
+    /// Every event recorded for `id`, oldest first. Events outlive a
+    /// purge of their item.
+    pub async fn get_events(&self, id: WorkId) -> Result<Vec<Event>> {
+        let rows: Vec<EventRow> = sqlx::query_as(
+            "SELECT seq, work_id, data, created_at
+             FROM work_events
+             WHERE work_id = $1
+             ORDER BY seq",
+        )
+        .bind(id.0)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(into_event).collect()
+    }
+
+    /// Every execution attempt of `id`, oldest first: when it started and
+    /// ended, and how. An attempt still running has no end.
+    ///
+    /// Attempts made before history was recorded don't appear, so this can
+    /// be shorter than the item's `attempts` count.
+    pub async fn get_attempt_history(&self, id: WorkId) -> Result<Vec<AttemptRecord>> {
+        type AttemptRow = (
+            i32,
+            chrono::DateTime<chrono::Utc>,
+            Option<chrono::DateTime<chrono::Utc>>,
+            Option<String>,
+            Option<String>,
+        );
+        let rows: Vec<AttemptRow> = sqlx::query_as(
+            "SELECT attempt_no, started_at, ended_at, outcome, error
+             FROM work_attempts
+             WHERE work_id = $1
+             ORDER BY attempt_no",
+        )
+        .bind(id.0)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|(attempt, started_at, ended_at, outcome, error)| {
+                Ok(AttemptRecord {
+                    attempt: attempt as u32,
+                    started_at,
+                    ended_at,
+                    outcome: outcome.map(|s| s.parse()).transpose()?,
+                    error,
+                })
+            })
+            .collect()
+    }
+
+    /// Merge count, time span, and sources of the duplicates merged into
+    /// `canonical` — a burst of many merges in a short span suggests the
+    /// producer wants debouncing.
+    ///
+    /// Like [`get_merged_provenance`](Self::get_merged_provenance), counts
+    /// only merged rows that haven't been purged.
+    pub async fn dedup_stats(&self, canonical: WorkId) -> Result<DedupStats> {
+        let row: DedupStatsRow = sqlx::query_as(
+            "SELECT count(*) AS merge_count, min(created_at) AS first_seen,
+                    max(created_at) AS last_seen,
+                    COALESCE(array_agg(DISTINCT source ORDER BY source), '{}') AS sources
+             FROM work_items
+             WHERE merged_into = $1",
+        )
+        .bind(canonical.0)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(DedupStats {
+            merge_count: row.merge_count as u64,
+            first_seen: row.first_seen,
+            last_seen: row.last_seen,
+            sources: row.sources,
+        })
+    }
+
+    /// The item `id` was ultimately merged into: `merged_into` followed
+    /// until an item that wasn't merged. Returns `id` itself if it wasn't.
+    ///
+    /// Submit only ever merges into an active item, so chains come from
+    /// imported or hand-edited data. A chain that loops is `InvalidState`.
+    pub async fn resolve_canonical(&self, id: WorkId) -> Result<WorkId> {
+        let row: Option<(Uuid, bool)> = sqlx::query_as(
+            "WITH RECURSIVE chain AS (
+                 SELECT id, merged_into, 0 AS depth, ARRAY[id] AS path, false AS cycle
+                 FROM work_items WHERE id = $1
+               UNION ALL
+                 SELECT w.id, w.merged_into, c.depth + 1, c.path || w.id, w.id = ANY(c.path)
+                 FROM work_items w JOIN chain c ON w.id = c.merged_into
+                 WHERE NOT c.cycle
+             )
+             SELECT id, cycle FROM chain ORDER BY depth DESC LIMIT 1",
+        )
+        .bind(id.0)
+        .fetch_optional(&self.pool)
+        .await?;
+        match row {
+            None => Err(Error::work_not_found(id)),
+            Some((_, true)) => Err(Error::InvalidState(format!("merge chain from {id} loops"))),
+            Some((canonical, false)) => Ok(WorkId(canonical)),
+        }
+    }
+
+    /// Point every merged item straight at its final canonical, collapsing
+    /// chains (A → B → C becomes A → C, B → C). Returns how many items
+    /// were repointed. Items on a loop are left as they are.
+    pub async fn compact_merge_chains(&self) -> Result<u64> {
+        let rewritten = sqlx::query(
+            "WITH RECURSIVE chain AS (
+                 SELECT id AS start, merged_into AS target, ARRAY[id] AS path, false AS cycle
+                 FROM work_items WHERE merged_into IS NOT NULL
+               UNION ALL
+                 SELECT c.start, w.merged_into, c.path || w.id, w.id = ANY(c.path)
+                 FROM chain c JOIN work_items w ON w.id = c.target
+                 WHERE w.merged_into IS NOT NULL AND NOT c.cycle
+             ),
+             canonical AS (
+                 SELECT start, target FROM chain c
+                 WHERE NOT EXISTS (
+                     SELECT 1 FROM work_items w WHERE w.id = c.target AND w.merged_into IS NOT NULL
+                 )
+             )
+             UPDATE work_items w SET merged_into = canonical.target
+             FROM canonical
+             WHERE w.id = canonical.start AND w.merged_into <> canonical.target",
+        )
         .execute(&self.pool)
         .await?
         .rows_affected();
+        Ok(rewritten)
+    }
+
+    /// Non-terminal work items whose deadline has passed, earliest first.
+    pub async fn list_overdue(&self) -> Result<Vec<WorkItem>> {
+        let rows: Vec<WorkItemRow> = sqlx::query_as(&format!(
+            "SELECT {WORK_ITEM_COLUMNS}
+             FROM work_items
+             WHERE deadline IS NOT NULL AND deadline < $1
+             AND state NOT IN ('completed', 'dead', 'merged')
+             ORDER BY deadline",
+        ))
+        .bind(self.clock.now())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(|r| r.try_into_work_item()).collect()
+    }
+
+    /// Dead-letter queued and failed items past their `expires_at` (see
+    /// [`NewWorkItem::ttl`]) with "ttl expired", archiving their queue
+    /// messages. Returns the expired ids.
+    ///
+    /// Claimed, running, and paused items are left alone; they expire once
+    /// they are back in Queued or have failed.
+    pub async fn expire_overdue(&self) -> Result<Vec<WorkId>> {
+        let mut tx = self.pool.begin().await?;
+        let expired = expire_due(&mut tx, self.clock.now(), None).await?;
+        tx.commit().await?;
+        record_expired(&expired);
+        Ok(expired.into_iter().map(|(id, _)| WorkId(id)).collect())
+    }
 
+    /// Like [`expire_overdue`](Self::expire_overdue), for `id` alone.
+    /// Returns whether it was expired.
+    pub async fn expire_work(&self, id: WorkId) -> Result<bool> {
+        let mut tx = self.pool.begin().await?;
+        let expired = expire_due(&mut tx, self.clock.now(), Some(id)).await?;
+        tx.commit().await?;
+        record_expired(&expired);
+        Ok(!expired.is_empty())
+    }
+
+    /// Claimed or running items idle past `policy`'s threshold for their
+    /// state, longest idle first. Finding them doesn't change them: alert
+    /// on the result, or recover the items separately.
+    pub async fn find_stale(&self, policy: &StalenessPolicy) -> Result<Vec<WorkItem>> {
+        let cutoff = |after: std::time::Duration| {
+            chrono::Duration::from_std(after)
+                .map(|after| self.clock.now() - after)
+                .map_err(|e| Error::Config(format!("invalid staleness threshold: {e}")))
+        };
+        let rows: Vec<WorkItemRow> = sqlx::query_as(&format!(
+            "SELECT {WORK_ITEM_COLUMNS}
+             FROM work_items
+             WHERE (state = 'claimed' AND updated_at < $1)
+             OR (state = 'running' AND updated_at < $2)
+             ORDER BY updated_at",
+        ))
+        .bind(cutoff(policy.claimed_after)?)
+        .bind(cutoff(policy.running_after)?)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(|r| r.try_into_work_item()).collect()
+    }
+
+    /// Work items carrying `tag`, newest first.
+    pub async fn list_by_tag(&self, tag: &str) -> Result<Vec<WorkItem>> {
+        let rows: Vec<WorkItemRow> = sqlx::query_as(&format!(
+            "SELECT {WORK_ITEM_COLUMNS}
+             FROM work_items
+             WHERE id IN (SELECT work_id FROM work_item_tags WHERE tag = $1)
+             ORDER BY created_at DESC",
+        ))
+        .bind(tag)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(|r| r.try_into_work_item()).collect()
+    }
+
+    /// Transition a work item's state with optimistic concurrency.
+    pub async fn transition_state(&self, id: WorkId, from: State, to: State) -> Result<WorkItem> {
+        validate_transition(id, from, to)?;
+
+        let rows_affected = apply_transition(&self.pool, id, from, to, self.clock.now()).await?;
         if rows_affected == 0 {
             return Err(Error::InvalidTransition {
                 from: from.to_string(),
                 to: to.to_string(),
+                work_id: Some(id),
             });
         }
+        record_transition(from, to);
+
+        self.get_work_item(id).await
+    }
+
+    /// Like [`transition_state`](Self::transition_state), but `guard` sees
+    /// the item first and can veto the transition by returning an error
+    /// (e.g. for access control). The row is locked from the guard check
+    /// until the update commits, so the guard's view can't go stale.
+    pub async fn transition_state_guarded(
+        &self,
+        id: WorkId,
+        from: State,
+        to: State,
+        guard: impl FnOnce(&WorkItem) -> Result<()>,
+    ) -> Result<WorkItem> {
+        validate_transition(id, from, to)?;
+
+        let mut tx = self.pool.begin().await?;
+        let row: Option<WorkItemRow> = sqlx::query_as(&format!(
+            "SELECT {WORK_ITEM_COLUMNS}
+             FROM work_items WHERE id = $1 FOR UPDATE",
+        ))
+        .bind(id.0)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let current = row
+            .ok_or_else(|| Error::work_not_found(id))?
+            .try_into_work_item()?;
+        if current.state != from {
+            return Err(Error::InvalidTransition {
+                from: current.state.to_string(),
+                to: to.to_string(),
+                work_id: Some(id),
+            });
+        }
+        guard(&current)?;
+
+        apply_transition(&mut *tx, id, from, to, self.clock.now()).await?;
+        tx.commit().await?;
+        record_transition(from, to);
+
+        self.get_work_item(id).await
+    }
+
+    /// Hold a queued item back from execution: Queued → Paused. Its queue
+    /// message may still be read, but consumers skip paused items.
+    pub async fn pause(&self, id: WorkId) -> Result<WorkItem> {
+        validate_transition(id, State::Queued, State::Paused)?;
+
+        let now = self.clock.now();
+        let mut tx = self.pool.begin().await?;
+        let rows_affected =
+            apply_transition(&mut *tx, id, State::Queued, State::Paused, now).await?;
+        if rows_affected == 0 {
+            let current = self.get_work_item(id).await?;
+            return Err(Error::InvalidTransition {
+                from: current.state.to_string(),
+                to: State::Paused.to_string(),
+                work_id: Some(id),
+            });
+        }
+        record_events(&mut *tx, &[id.0], &EventKind::WorkPaused, now).await?;
+        tx.commit().await?;
+        record_transition(State::Queued, State::Paused);
+
+        self.get_work_item(id).await
+    }
 
+    /// Release a paused item: Paused → Queued, with its queue message made
+    /// visible straight away (or re-sent if pgmq no longer holds it).
+    pub async fn resume(&self, id: WorkId) -> Result<WorkItem> {
+        validate_transition(id, State::Paused, State::Queued)?;
+
+        let mut tx = self.pool.begin().await?;
+        let row: Option<(String, String, Option<i64>)> = sqlx::query_as(
+            "SELECT state, queue_name, pgmq_msg_id FROM work_items WHERE id = $1 FOR UPDATE",
+        )
+        .bind(id.0)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let (state, queue, msg_id) = row.ok_or_else(|| Error::work_not_found(id))?;
+        if state != "paused" {
+            return Err(Error::InvalidTransition {
+                from: state,
+                to: State::Queued.to_string(),
+                work_id: Some(id),
+            });
+        }
+
+        let now = self.clock.now();
+        apply_transition(&mut *tx, id, State::Paused, State::Queued, now).await?;
+        record_events(&mut *tx, &[id.0], &EventKind::WorkResumed, now).await?;
+        make_visible(&mut tx, id, &queue, msg_id).await?;
+        tx.commit().await?;
+        record_transition(State::Paused, State::Queued);
+
+        self.get_work_item(id).await
+    }
+
+    /// Pause every queued item of `faculty`. Returns how many were paused.
+    /// Work submitted afterwards is not paused.
+    pub async fn pause_by_faculty(&self, faculty: &str) -> Result<u64> {
+        let now = self.clock.now();
+        let mut tx = self.pool.begin().await?;
+        let paused: Vec<Uuid> = sqlx::query_scalar(
+            "UPDATE work_items SET state = 'paused', updated_at = $2
+             WHERE faculty = $1 AND state = 'queued'
+             RETURNING id",
+        )
+        .bind(faculty)
+        .bind(now)
+        .fetch_all(&mut *tx)
+        .await?;
+        let state_changed = EventKind::StateChanged {
+            from: State::Queued,
+            to: State::Paused,
+        };
+        record_events(&mut *tx, &paused, &state_changed, now).await?;
+        record_events(&mut *tx, &paused, &EventKind::WorkPaused, now).await?;
+        tx.commit().await?;
+        let paused = paused.len() as u64;
         metrics::work_state_transitions().add(
-            1,
+            paused,
             &[
-                KeyValue::new("from", from.to_string()),
-                KeyValue::new("to", to.to_string()),
+                KeyValue::new("from", "queued"),
+                KeyValue::new("to", "paused"),
             ],
         );
+        Ok(paused)
+    }
+
+    /// Resume every paused item of `faculty`. Returns how many were resumed.
+    pub async fn resume_by_faculty(&self, faculty: &str) -> Result<u64> {
+        let now = self.clock.now();
+        let mut tx = self.pool.begin().await?;
+        let resumed: Vec<(Uuid, String, Option<i64>)> = sqlx::query_as(
+            "UPDATE work_items SET state = 'queued', updated_at = $2
+             WHERE faculty = $1 AND state = 'paused'
+             RETURNING id, queue_name, pgmq_msg_id",
+        )
+        .bind(faculty)
+        .bind(now)
+        .fetch_all(&mut *tx)
+        .await?;
+        let ids: Vec<Uuid> = resumed.iter().map(|(id, ..)| *id).collect();
+        let state_changed = EventKind::StateChanged {
+            from: State::Paused,
+            to: State::Queued,
+        };
+        record_events(&mut *tx, &ids, &state_changed, now).await?;
+        record_events(&mut *tx, &ids, &EventKind::WorkResumed, now).await?;
+        for (id, queue, msg_id) in &resumed {
+            make_visible(&mut tx, WorkId(*id), queue, *msg_id).await?;
+        }
+        tx.commit().await?;
+        metrics::work_state_transitions().add(
+            resumed.len() as u64,
+            &[
+                KeyValue::new("from", "paused"),
+                KeyValue::new("to", "queued"),
+            ],
+        );
+        Ok(resumed.len() as u64)
+    }
+
+    /// Change the priority of a still-queued item, e.g. to escalate an
+    /// urgent request. Fails with `InvalidState` once the item has left
+    /// `Queued`.
+    ///
+    /// The item's message is re-sent carrying the new priority, so it is
+    /// claimed ahead of lower-priority work already waiting; among work of
+    /// its new priority it goes to the back.
+    pub async fn bump_priority(&self, id: WorkId, new_priority: i32) -> Result<WorkItem> {
+        if let Some(ref scheme) = self.priority_scheme {
+            scheme.check(new_priority)?;
+        }
+        let mut tx = self.pool.begin().await?;
+        let row: Option<(String, i32, String, Option<i64>)> = sqlx::query_as(
+            "SELECT state, priority, queue_name, pgmq_msg_id FROM work_items
+             WHERE id = $1 FOR UPDATE",
+        )
+        .bind(id.0)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let (state, from, queue, msg_id) = row.ok_or_else(|| Error::work_not_found(id))?;
+        if state != "queued" {
+            return Err(Error::InvalidState(format!(
+                "cannot reprioritize {id}: it is {state}, not queued"
+            )));
+        }
+        let now = self.clock.now();
+        sqlx::query("UPDATE work_items SET priority = $2, updated_at = $3 WHERE id = $1")
+            .bind(id.0)
+            .bind(new_priority)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+        if new_priority != from {
+            if let Some(msg_id) = msg_id {
+                sqlx::query("SELECT pgmq.archive($1, $2)")
+                    .bind(&queue)
+                    .bind(msg_id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            resend(&mut tx, id, &queue, std::time::Duration::ZERO).await?;
+        }
+        let reprioritized = EventKind::Reprioritized {
+            from,
+            to: new_priority,
+        };
+        record_events(&mut *tx, &[id.0], &reprioritized, now).await?;
+        tx.commit().await?;
+        tracing::info!(work_id = %id, from, to = new_priority, "work reprioritized");
 
         self.get_work_item(id).await
     }
 
     /// Complete a work item: Running → Completed with outcome data.
+    ///
+    /// Idempotent: if the item is already completed with the same outcome
+    /// data and error (a retried call whose first attempt committed), the
+    /// completed item is returned. Any other state is `InvalidTransition`.
     pub async fn complete_work(&self, id: WorkId, outcome: Outcome) -> Result<WorkItem> {
-        validate_transition(State::Running, State::Completed)?;
+        validate_transition(id, State::Running, State::Completed)?;
 
-        let now = chrono::Utc::now();
+        let now = self.clock.now();
+        // The attempt row closes and the event is logged in the same
+        // statement, only if the item is still running
         let rows_affected = sqlx::query(
-            "UPDATE work_items SET state = 'completed', updated_at = $1, resolved_at = $1, outcome_data = $2, outcome_error = $3, outcome_ms = $4
+            "WITH ended AS (
+                 UPDATE work_attempts SET ended_at = $1, outcome = 'completed', error = $3
+                 WHERE work_id = $5 AND ended_at IS NULL
+                 AND EXISTS (SELECT 1 FROM work_items WHERE id = $5 AND state = 'running')
+             ),
+             logged AS (
+                 INSERT INTO work_events (work_id, kind, data, created_at)
+                 SELECT $5, 'state_changed', $6, $1
+                 WHERE EXISTS (SELECT 1 FROM work_items WHERE id = $5 AND state = 'running')
+             )
+             UPDATE work_items SET state = 'completed', updated_at = $1, resolved_at = $1, outcome_data = $2, outcome_error = $3, outcome_ms = $4
              WHERE id = $5 AND state = 'running'",
         )
         .bind(now)
@@ -305,14 +1985,30 @@ impl super::Db {
         .bind(&outcome.error)
         .bind(outcome.duration_ms as i64)
         .bind(id.0)
+        .bind(event_data(&EventKind::StateChanged {
+            from: State::Running,
+            to: State::Completed,
+        }))
         .execute(&self.pool)
         .await?
         .rows_affected();
 
         if rows_affected == 0 {
+            // A replayed call whose first attempt already committed is a
+            // success, not a conflict.
+            let current = self.get_work_item(id).await?;
+            let replayed = current.state == State::Completed
+                && current
+                    .outcome
+                    .as_ref()
+                    .is_some_and(|o| o.data == outcome.data && o.error == outcome.error);
+            if replayed {
+                return Ok(current);
+            }
             return Err(Error::InvalidTransition {
-                from: "running".to_string(),
+                from: current.state.to_string(),
                 to: "completed".to_string(),
+                work_id: Some(id),
             });
         }
 
@@ -332,52 +2028,414 @@ impl super::Db {
     }
 
     /// Fail a work item: Running → Failed with error info.
+    ///
+    /// Idempotent like [`complete_work`](Self::complete_work): repeating a
+    /// committed call with the same error returns the failed item.
     pub async fn fail_work(&self, id: WorkId, error: &str, duration_ms: u64) -> Result<WorkItem> {
-        validate_transition(State::Running, State::Failed)?;
+        self.fail_work_with_data(id, error, None, duration_ms).await
+    }
 
-        let now = chrono::Utc::now();
-        let rows_affected = sqlx::query(
-            "UPDATE work_items SET state = 'failed', updated_at = $1, outcome_error = $2, outcome_ms = $3
-             WHERE id = $4 AND state = 'running'",
+    /// Like [`fail_work`](Self::fail_work), but also stores structured
+    /// diagnostics (an error code, HTTP status, ...) as the outcome data so
+    /// callers can branch on them instead of parsing the message.
+    pub async fn fail_work_with_data(
+        &self,
+        id: WorkId,
+        error: &str,
+        data: Option<serde_json::Value>,
+        duration_ms: u64,
+    ) -> Result<WorkItem> {
+        validate_transition(id, State::Running, State::Failed)?;
+
+        let rows_affected = mark_failed(
+            &self.pool,
+            id,
+            error,
+            data.as_ref(),
+            duration_ms,
+            self.clock.now(),
         )
-        .bind(now)
-        .bind(error)
-        .bind(duration_ms as i64)
-        .bind(id.0)
-        .execute(&self.pool)
-        .await?
-        .rows_affected();
+        .await?;
 
         if rows_affected == 0 {
+            // Same as complete_work: tolerate a replay of a committed fail.
+            let current = self.get_work_item(id).await?;
+            let replayed = current.state == State::Failed
+                && current
+                    .outcome
+                    .as_ref()
+                    .is_some_and(|o| o.error.as_deref() == Some(error) && o.data == data);
+            if replayed {
+                return Ok(current);
+            }
             return Err(Error::InvalidTransition {
-                from: "running".to_string(),
+                from: current.state.to_string(),
                 to: "failed".to_string(),
+                work_id: Some(id),
             });
         }
 
-        metrics::work_state_transitions().add(
-            1,
-            &[
-                KeyValue::new("from", "running"),
-                KeyValue::new("to", "failed"),
-            ],
-        );
-        metrics::operation_duration_ms().record(
-            duration_ms as f64,
-            &[KeyValue::new("operation", "work.execute")],
-        );
+        record_failed(duration_ms);
+
+        self.get_work_item(id).await
+    }
+
+    /// Fail a running item and settle it by `class`: retryable failures
+    /// go back to Queued (after the delay, for `RetryAfter`) while attempts
+    /// remain, and everything else goes to Dead with its message archived.
+    /// Failing and settling commit together, so the item is never left
+    /// Failed with nothing to retry or dead-letter it.
+    ///
+    /// The item's queue message is reused for the retry, so it becomes
+    /// visible again exactly when the retry is due. Repeating a committed
+    /// call with the same error returns the settled item unchanged.
+    pub async fn fail_with_policy(
+        &self,
+        id: WorkId,
+        error: &str,
+        duration_ms: u64,
+        class: FailureClass,
+    ) -> Result<WorkItem> {
+        validate_transition(id, State::Running, State::Failed)?;
+
+        let mut tx = self.pool.begin().await?;
+        let row: Option<SettleRow> = sqlx::query_as(
+            "SELECT state, queue_name, pgmq_msg_id, attempts, max_attempts, outcome_error
+                 FROM work_items WHERE id = $1 FOR UPDATE",
+        )
+        .bind(id.0)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let (state, queue, msg_id, attempts, max_attempts, outcome_error) =
+            row.ok_or_else(|| Error::work_not_found(id))?;
+        let state: State = state.parse()?;
+        let same_error = outcome_error.as_deref() == Some(error);
+        let now = self.clock.now();
+        let newly_failed = match state {
+            State::Running => {
+                mark_failed(&mut *tx, id, error, None, duration_ms, now).await?;
+                true
+            }
+            // Failed by fail_work but not yet settled
+            State::Failed if same_error => false,
+            // A replay of a call that already settled the item
+            State::Queued | State::Dead if same_error => {
+                drop(tx);
+                return self.get_work_item(id).await;
+            }
+            state => {
+                return Err(Error::InvalidTransition {
+                    from: state.to_string(),
+                    to: State::Failed.to_string(),
+                    work_id: Some(id),
+                });
+            }
+        };
+
+        let max_attempts = max_attempts.map_or(DEFAULT_MAX_ATTEMPTS, |n| n as u32);
+        let delay = match class {
+            FailureClass::Retryable => Some(std::time::Duration::ZERO),
+            FailureClass::RetryAfter(delay) => Some(delay),
+            FailureClass::NonRetryable => None,
+        }
+        .filter(|_| (attempts as u32) < max_attempts);
+
+        let to = if delay.is_some() {
+            State::Queued
+        } else {
+            State::Dead
+        };
+        validate_transition(id, State::Failed, to)?;
+        sqlx::query(
+            "UPDATE work_items SET state = $1, updated_at = $3,
+                resolved_at = CASE WHEN $1 = 'dead' THEN $3 ELSE resolved_at END
+             WHERE id = $2",
+        )
+        .bind(to.to_string())
+        .bind(id.0)
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+        let state_changed = EventKind::StateChanged {
+            from: State::Failed,
+            to,
+        };
+        record_events(&mut *tx, &[id.0], &state_changed, now).await?;
+
+        match (delay, msg_id) {
+            (Some(delay), Some(msg_id)) => {
+                let moved: Option<(i64,)> =
+                    sqlx::query_as("SELECT msg_id FROM pgmq.set_vt($1, $2, $3)")
+                        .bind(&queue)
+                        .bind(msg_id)
+                        .bind(delay.as_secs() as i32)
+                        .fetch_optional(&mut *tx)
+                        .await?;
+                if moved.is_none() {
+                    resend(&mut tx, id, &queue, delay).await?;
+                }
+            }
+            (Some(delay), None) => resend(&mut tx, id, &queue, delay).await?,
+            (None, Some(msg_id)) => {
+                sqlx::query("SELECT pgmq.archive($1, $2)")
+                    .bind(&queue)
+                    .bind(msg_id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            (None, None) => {}
+        }
+        tx.commit().await?;
+
+        if newly_failed {
+            record_failed(duration_ms);
+        }
+        record_transition(State::Failed, to);
+
+        self.get_work_item(id).await
+    }
+
+    /// Put a claimed item back on its queue: Claimed → Queued, for a
+    /// consumer that claimed work but couldn't start it. No attempt is
+    /// counted. The item's queue message is made visible right away (or
+    /// re-sent, if it's gone) so the next read can claim it again.
+    pub async fn requeue_claimed(&self, id: WorkId) -> Result<()> {
+        validate_transition(id, State::Claimed, State::Queued)?;
+
+        let mut tx = self.pool.begin().await?;
+        let row: Option<(String, String, Option<i64>)> = sqlx::query_as(
+            "SELECT state, queue_name, pgmq_msg_id
+             FROM work_items WHERE id = $1 FOR UPDATE",
+        )
+        .bind(id.0)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let (state, queue, msg_id) = row.ok_or_else(|| Error::work_not_found(id))?;
+        if state != State::Claimed.to_string() {
+            return Err(Error::InvalidTransition {
+                from: state,
+                to: State::Queued.to_string(),
+                work_id: Some(id),
+            });
+        }
+
+        apply_transition(
+            &mut *tx,
+            id,
+            State::Claimed,
+            State::Queued,
+            self.clock.now(),
+        )
+        .await?;
+        make_visible(&mut tx, id, &queue, msg_id).await?;
+        tx.commit().await?;
+        record_transition(State::Claimed, State::Queued);
+        Ok(())
+    }
+
+    /// Count a read that found no faculty for `id`, returning the item's
+    /// unroutable reads so far. Kept apart from pgmq's `read_ct`, which
+    /// also counts retries, pauses and accept-filter skips.
+    pub async fn note_unroutable(&self, id: WorkId) -> Result<u32> {
+        let reads: Option<i32> = sqlx::query_scalar(
+            "UPDATE work_items SET unroutable_reads = unroutable_reads + 1
+             WHERE id = $1 RETURNING unroutable_reads",
+        )
+        .bind(id.0)
+        .fetch_optional(&self.pool)
+        .await?;
+        let reads = reads.ok_or_else(|| Error::work_not_found(id))?;
+        Ok(reads as u32)
+    }
+
+    /// Dead-letter a queued work item without running it: Queued → Dead,
+    /// recording `reason` as the outcome error. Its queue message is
+    /// archived in the same transaction.
+    pub async fn dead_letter(&self, id: WorkId, reason: &str) -> Result<WorkItem> {
+        validate_transition(id, State::Queued, State::Dead)?;
+
+        let mut tx = self.pool.begin().await?;
+        let row: Option<(String, String, Option<i64>)> = sqlx::query_as(
+            "SELECT state, queue_name, pgmq_msg_id FROM work_items WHERE id = $1 FOR UPDATE",
+        )
+        .bind(id.0)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let (state, queue, msg_id) = row.ok_or_else(|| Error::work_not_found(id))?;
+        if state != "queued" {
+            return Err(Error::InvalidTransition {
+                from: state,
+                to: State::Dead.to_string(),
+                work_id: Some(id),
+            });
+        }
+
+        let now = self.clock.now();
+        sqlx::query(
+            "UPDATE work_items SET state = 'dead', updated_at = $1, resolved_at = $1, outcome_error = $2
+             WHERE id = $3",
+        )
+        .bind(now)
+        .bind(reason)
+        .bind(id.0)
+        .execute(&mut *tx)
+        .await?;
+        let state_changed = EventKind::StateChanged {
+            from: State::Queued,
+            to: State::Dead,
+        };
+        record_events(&mut *tx, &[id.0], &state_changed, now).await?;
+        if let Some(msg_id) = msg_id {
+            sqlx::query("SELECT pgmq.archive($1, $2)")
+                .bind(&queue)
+                .bind(msg_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+        record_transition(State::Queued, State::Dead);
 
         self.get_work_item(id).await
     }
+
+    /// Put a dead or failed item back in its queue for a fresh run:
+    /// Dead/Failed → Queued with attempts and unroutable reads reset and the
+    /// previous outcome cleared. Any message left from the previous run is
+    /// archived and a fresh one sent.
+    ///
+    /// Fails with `InvalidState` if an active item now holds the same dedup
+    /// key, since the replayed item would duplicate it.
+    pub async fn replay_dead_letter(&self, id: WorkId) -> Result<WorkItem> {
+        let mut tx = self.pool.begin().await?;
+        let row: Option<(String, String, Option<i64>)> = sqlx::query_as(
+            "SELECT state, queue_name, pgmq_msg_id FROM work_items WHERE id = $1 FOR UPDATE",
+        )
+        .bind(id.0)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let (state, queue, msg_id) = row.ok_or_else(|| Error::work_not_found(id))?;
+        let from: State = state
+            .parse()
+            .map_err(|_| Error::InvalidState(format!("unknown state: {state}")))?;
+        if !matches!(from, State::Dead | State::Failed) {
+            return Err(Error::InvalidTransition {
+                from: from.to_string(),
+                to: State::Queued.to_string(),
+                work_id: Some(id),
+            });
+        }
+        validate_transition(id, from, State::Queued)?;
+
+        let now = self.clock.now();
+        let updated = sqlx::query(
+            "UPDATE work_items SET state = 'queued', updated_at = $2, resolved_at = NULL,
+                attempts = 0, outcome_data = NULL, outcome_error = NULL, outcome_ms = NULL,
+                unroutable_reads = 0
+             WHERE id = $1",
+        )
+        .bind(id.0)
+        .bind(now)
+        .execute(&mut *tx)
+        .await;
+        if let Err(sqlx::Error::Database(e)) = &updated
+            && e.is_unique_violation()
+        {
+            return Err(Error::InvalidState(format!(
+                "cannot replay {id}: an active work item has the same dedup key"
+            )));
+        }
+        updated?;
+        if let Some(msg_id) = msg_id {
+            sqlx::query("SELECT pgmq.archive($1, $2)")
+                .bind(&queue)
+                .bind(msg_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        resend(&mut tx, id, &queue, std::time::Duration::ZERO).await?;
+        let state_changed = EventKind::StateChanged {
+            from,
+            to: State::Queued,
+        };
+        record_events(&mut *tx, &[id.0], &state_changed, now).await?;
+        tx.commit().await?;
+        record_transition(from, State::Queued);
+
+        self.get_work_item(id).await
+    }
+
+    /// Delete terminal work items (completed, dead, merged) resolved before
+    /// `now - older_than`. Returns the number of rows deleted.
+    ///
+    /// Runs in a single transaction. Items still referenced by a surviving
+    /// row — as its `merged_into` target or `parent_id` — are kept, so the
+    /// self-referencing foreign keys are never violated. Each deleted item
+    /// gets an [`EventKind::WorkPurged`] carrying the purge's total.
+    pub async fn purge_completed(&self, older_than: std::time::Duration) -> Result<u64> {
+        let older_than = chrono::Duration::from_std(older_than)
+            .map_err(|e| Error::Other(format!("invalid retention period: {e}")))?;
+        let now = self.clock.now();
+        let cutoff = now - older_than;
+
+        let mut tx = self.pool.begin().await?;
+
+        // Every row that survives the purge protects the rows it points at,
+        // transitively. Whatever purgeable row is left unprotected can go.
+        // The event log has no foreign key, so its entries outlive the rows.
+        let (deleted,): (i64,) = sqlx::query_as(
+            "WITH RECURSIVE protected AS (
+                 SELECT id, merged_into, parent_id FROM work_items
+                 WHERE NOT COALESCE(state IN ('completed', 'dead', 'merged') AND resolved_at < $1, false)
+               UNION
+                 SELECT w.id, w.merged_into, w.parent_id FROM work_items w
+                 JOIN protected p ON w.id = p.merged_into OR w.id = p.parent_id
+             ),
+             purged AS (
+                 DELETE FROM work_items
+                 WHERE state IN ('completed', 'dead', 'merged') AND resolved_at < $1
+                 AND id NOT IN (SELECT id FROM protected)
+                 RETURNING id
+             ),
+             logged AS (
+                 INSERT INTO work_events (work_id, kind, data, created_at)
+                 SELECT id, 'work_purged', $2 || jsonb_build_object('count', (SELECT count(*) FROM purged)), $3
+                 FROM purged
+             )
+             SELECT count(*) FROM purged",
+        )
+        .bind(cutoff)
+        .bind(event_data(&EventKind::WorkPurged { count: 0 }))
+        .bind(now)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        let deleted = deleted as u64;
+        metrics::work_purged().add(deleted, &[]);
+        tracing::info!(count = deleted, %cutoff, "purged resolved work");
+
+        Ok(deleted)
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct DedupStatsRow {
+    merge_count: i64,
+    first_seen: Option<chrono::DateTime<chrono::Utc>>,
+    last_seen: Option<chrono::DateTime<chrono::Utc>>,
+    sources: Vec<String>,
 }
 
 /// Internal row type for sqlx::FromRow.
 #[derive(sqlx::FromRow)]
-struct WorkItemRow {
+pub(super) struct WorkItemRow {
     id: Uuid,
+    queue_name: String,
     faculty: String,
     skill: Option<String>,
     dedup_key: Option<String>,
+    dedup_scope: Option<String>,
     source: String,
     trigger_info: Option<String>,
     params: serde_json::Value,
@@ -393,10 +2451,14 @@ struct WorkItemRow {
     outcome_data: Option<serde_json::Value>,
     outcome_error: Option<String>,
     outcome_ms: Option<i64>,
+    trace_context: Option<serde_json::Value>,
+    deadline: Option<chrono::DateTime<chrono::Utc>>,
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    tags: Vec<String>,
 }
 
 impl WorkItemRow {
-    fn try_into_work_item(self) -> Result<WorkItem> {
+    pub(super) fn try_into_work_item(self) -> Result<WorkItem> {
         let outcome = if self.outcome_data.is_some() || self.outcome_error.is_some() {
             Some(Outcome {
                 success: self.outcome_error.is_none(),
@@ -410,9 +2472,11 @@ impl WorkItemRow {
 
         Ok(WorkItem {
             id: WorkId(self.id),
+            queue: self.queue_name,
             faculty: self.faculty,
             skill: self.skill,
             dedup_key: self.dedup_key,
+            dedup_scope: self.dedup_scope,
             provenance: Provenance {
                 source: self.source,
                 trigger: self.trigger_info,
@@ -428,6 +2492,12 @@ impl WorkItemRow {
             updated_at: self.updated_at,
             resolved_at: self.resolved_at,
             outcome,
+            trace_context: self
+                .trace_context
+                .and_then(|v| serde_json::from_value(v).ok()),
+            deadline: self.deadline,
+            expires_at: self.expires_at,
+            tags: self.tags,
         })
     }
 }
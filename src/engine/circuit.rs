@@ -0,0 +1,104 @@
+//! Per-faculty circuit breaker: stop feeding work to a faculty that keeps
+//! failing, so an outage downstream isn't met with a retry storm.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// When a faculty's circuit trips and for how long it stays open.
+#[derive(Debug, Clone)]
+pub struct CircuitConfig {
+    /// Failures within `window` that open the circuit.
+    pub threshold: usize,
+    /// How far back failures are counted.
+    pub window: Duration,
+    /// How long the circuit stays open before work flows again.
+    pub cooldown: Duration,
+}
+
+impl Default for CircuitConfig {
+    fn default() -> Self {
+        Self {
+            threshold: 5,
+            window: Duration::from_secs(60),
+            cooldown: Duration::from_secs(300),
+        }
+    }
+}
+
+/// Whether work for a faculty is currently allowed through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Work flows normally.
+    Closed,
+    /// Too many recent failures; new work is dead-lettered until the
+    /// cooldown ends.
+    Open { remaining: Duration },
+}
+
+#[derive(Default)]
+struct Circuit {
+    failures: VecDeque<Instant>,
+    open_until: Option<Instant>,
+}
+
+/// Rolling failure counts per faculty.
+pub struct CircuitBreaker {
+    config: CircuitConfig,
+    circuits: Mutex<HashMap<String, Circuit>>,
+}
+
+impl CircuitBreaker {
+    pub fn new(config: CircuitConfig) -> Self {
+        Self {
+            config,
+            circuits: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Current state of `faculty`'s circuit. A circuit whose cooldown has
+    /// elapsed closes with its failure history cleared.
+    pub fn state(&self, faculty: &str) -> CircuitState {
+        let now = Instant::now();
+        let mut circuits = self.circuits.lock().expect("circuit lock poisoned");
+        let Some(circuit) = circuits.get_mut(faculty) else {
+            return CircuitState::Closed;
+        };
+        match circuit.open_until {
+            Some(until) if until > now => CircuitState::Open {
+                remaining: until - now,
+            },
+            Some(_) => {
+                *circuit = Circuit::default();
+                CircuitState::Closed
+            }
+            None => CircuitState::Closed,
+        }
+    }
+
+    /// Record a failed execution; opens the circuit once `threshold`
+    /// failures fall within `window`. Returns whether this failure tripped it.
+    pub fn record_failure(&self, faculty: &str) -> bool {
+        let now = Instant::now();
+        let mut circuits = self.circuits.lock().expect("circuit lock poisoned");
+        let circuit = circuits.entry(faculty.to_string()).or_default();
+        if circuit.open_until.is_some() {
+            return false;
+        }
+
+        circuit.failures.push_back(now);
+        while circuit
+            .failures
+            .front()
+            .is_some_and(|t| now.duration_since(*t) > self.config.window)
+        {
+            circuit.failures.pop_front();
+        }
+        if circuit.failures.len() >= self.config.threshold {
+            circuit.open_until = Some(now + self.config.cooldown);
+            circuit.failures.clear();
+            return true;
+        }
+        false
+    }
+}
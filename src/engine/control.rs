@@ -1,22 +1,56 @@
 //! Control plane: listens for work, routes to faculties, manages focus lifecycle.
 
 use crate::db::Db;
+use crate::db::pgmq::{PgmqMessage, WORK_PAYLOAD_VERSION, WorkPayload};
 use crate::error::{Error, Result};
-use crate::faculty::FacultyRegistry;
-use crate::model::work::{Outcome, State, WorkId};
+use crate::faculty::{FacultyMeta, FacultyRegistry};
+use crate::model::work::{FailureClass, Outcome, State, WorkId, WorkItem};
 use crate::telemetry::{
     metrics,
-    work::{record_state_transition, start_work_span},
+    work::{record_state_transition, start_work_span, start_work_span_with_parent},
 };
 use opentelemetry::KeyValue;
-use std::path::PathBuf;
-use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
 use tokio::sync::Notify;
-use tracing::{Instrument, error, info, warn};
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
+use tracing::{Instrument, Span, debug, error, info, warn};
 use uuid::Uuid;
 
-use super::focus::{Focus, FocusResult};
+use super::circuit::{CircuitBreaker, CircuitConfig, CircuitState};
+use super::focus::{DEFAULT_MAX_PARAMS_BYTES, Focus, FocusResult};
+use super::health::{ComponentHealth, HealthReport};
+
+/// How long cancelled foci get to record their failure once a drain has
+/// timed out.
+const CANCEL_GRACE: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// A pgmq queue the control plane consumes.
+#[derive(Debug, Clone)]
+pub struct QueueConfig {
+    /// Queue name; new work is announced on the `{name}_ready` channel.
+    pub name: String,
+    /// Cap on foci from this queue, within the global limit. None = only
+    /// the global limit applies.
+    pub max_concurrent: Option<usize>,
+}
+
+impl QueueConfig {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            max_concurrent: None,
+        }
+    }
+
+    pub fn max_concurrent(mut self, n: usize) -> Self {
+        self.max_concurrent = Some(n);
+        self
+    }
+}
 
 /// Configuration for the control plane.
 #[derive(Debug, Clone)]
@@ -27,6 +61,31 @@ pub struct ControlConfig {
     pub visibility_timeout: i32,
     /// Poll interval fallback when no NOTIFY arrives.
     pub poll_interval: std::time::Duration,
+    /// How long shutdown waits for in-flight foci before abandoning them
+    /// to visibility-timeout recovery.
+    pub drain_timeout: std::time::Duration,
+    /// Queues to consume, served in order when filling capacity.
+    pub queues: Vec<QueueConfig>,
+    /// Per-faculty circuit breaker. None = never trip.
+    pub circuit: Option<CircuitConfig>,
+    /// Reads finding no registered faculty after which the work is
+    /// dead-lettered. Counted per item, apart from pgmq's read count.
+    pub unroutable_max_reads: u32,
+    /// Ceiling on the backoff between reads of unroutable work, which
+    /// starts at the visibility timeout and doubles per read.
+    pub unroutable_backoff_cap: std::time::Duration,
+    /// Focus directories with no live focus are swept once older than this.
+    pub orphan_age: std::time::Duration,
+    /// Total size of `focus_base_dir` above which no new foci are spawned;
+    /// queued work waits until space is freed. None = unlimited.
+    pub focus_quota_bytes: Option<u64>,
+    /// Largest serialized `params` a focus is created for. Bigger work is
+    /// failed without retry.
+    pub max_params_bytes: usize,
+    /// Work types (faculties) this control plane serves. None = all.
+    pub accept_types: Option<Vec<String>>,
+    /// Work types this control plane never serves, even if accepted above.
+    pub reject_types: Vec<String>,
 }
 
 impl Default for ControlConfig {
@@ -35,17 +94,49 @@ impl Default for ControlConfig {
             focus_base_dir: PathBuf::from("/tmp/animus-foci"),
             visibility_timeout: 60,
             poll_interval: std::time::Duration::from_secs(5),
+            drain_timeout: std::time::Duration::from_secs(30),
+            queues: vec![QueueConfig::new("work")],
+            circuit: None,
+            unroutable_max_reads: 10,
+            unroutable_backoff_cap: std::time::Duration::from_secs(3600),
+            orphan_age: std::time::Duration::from_secs(3600),
+            focus_quota_bytes: None,
+            max_params_bytes: DEFAULT_MAX_PARAMS_BYTES,
+            accept_types: None,
+            reject_types: Vec::new(),
         }
     }
 }
 
+impl ControlConfig {
+    /// Whether work for `faculty` is served here, per `accept_types` and
+    /// `reject_types`.
+    pub fn accepts(&self, faculty: &str) -> bool {
+        self.accept_types
+            .as_ref()
+            .is_none_or(|types| types.iter().any(|t| t == faculty))
+            && !self.reject_types.iter().any(|t| t == faculty)
+    }
+}
+
 /// The control plane loop: listen for work, spawn foci, retire items.
 pub struct ControlPlane {
     db: Arc<Db>,
-    registry: Arc<FacultyRegistry>,
+    /// Swapped wholesale by `reload_faculties`; readers take a snapshot.
+    registry: Arc<RwLock<Arc<FacultyRegistry>>>,
     config: ControlConfig,
     shutdown: Arc<Notify>,
+    /// Parent of every focus's cancellation token; cancelled when a drain
+    /// times out.
+    cancel: CancellationToken,
+    /// Cancellation tokens of running foci, by work item.
+    running: Arc<Mutex<HashMap<WorkId, CancellationToken>>>,
     active_foci: Arc<AtomicUsize>,
+    /// Active foci per queue, for per-queue limits.
+    queue_foci: Arc<HashMap<String, Arc<AtomicUsize>>>,
+    circuit: Option<Arc<CircuitBreaker>>,
+    /// Ids of foci whose directories are in use, spared by the orphan sweep.
+    live_foci: Arc<Mutex<HashSet<Uuid>>>,
     max_concurrent: usize,
 }
 
@@ -56,7 +147,12 @@ impl Clone for ControlPlane {
             registry: Arc::clone(&self.registry),
             config: self.config.clone(),
             shutdown: Arc::clone(&self.shutdown),
+            cancel: self.cancel.clone(),
+            running: Arc::clone(&self.running),
             active_foci: Arc::clone(&self.active_foci),
+            queue_foci: Arc::clone(&self.queue_foci),
+            circuit: self.circuit.clone(),
+            live_foci: Arc::clone(&self.live_foci),
             max_concurrent: self.max_concurrent,
         }
     }
@@ -69,185 +165,800 @@ impl ControlPlane {
         config: ControlConfig,
         max_concurrent: usize,
     ) -> Self {
+        let queue_foci = config
+            .queues
+            .iter()
+            .map(|q| (q.name.clone(), Arc::new(AtomicUsize::new(0))))
+            .collect();
+        let circuit = config
+            .circuit
+            .clone()
+            .map(|c| Arc::new(CircuitBreaker::new(c)));
         Self {
             db,
-            registry,
+            registry: Arc::new(RwLock::new(registry)),
             config,
             shutdown: Arc::new(Notify::new()),
+            cancel: CancellationToken::new(),
+            running: Arc::new(Mutex::new(HashMap::new())),
             active_foci: Arc::new(AtomicUsize::new(0)),
+            queue_foci: Arc::new(queue_foci),
+            circuit,
+            live_foci: Arc::new(Mutex::new(HashSet::new())),
             max_concurrent,
         }
     }
 
-    /// Signal the control plane to shut down.
+    /// Circuit state for `faculty`. Always closed when no breaker is configured.
+    pub fn circuit_state(&self, faculty: &str) -> CircuitState {
+        self.circuit
+            .as_ref()
+            .map_or(CircuitState::Closed, |c| c.state(faculty))
+    }
+
+    /// Readiness of everything the control plane depends on: the database
+    /// answers, pgmq is installed, no migrations are pending, every
+    /// configured queue exists, and at least one faculty is loaded. Later
+    /// checks that need pgmq are skipped when it is missing.
+    pub async fn health(&self) -> HealthReport {
+        let mut components = Vec::new();
+
+        let database = self.db.health_check().await;
+        let db_up = database.is_ok();
+        components.push(match database {
+            Ok(()) => ComponentHealth::ok("database"),
+            Err(e) => ComponentHealth::failed("database", e.to_string()),
+        });
+
+        if db_up {
+            components.push(match self.db.pending_migrations().await {
+                Ok(0) => ComponentHealth::ok("migrations"),
+                Ok(n) => ComponentHealth::failed("migrations", format!("{n} pending")),
+                Err(e) => ComponentHealth::failed("migrations", e.to_string()),
+            });
+
+            let pgmq = self.db.pgmq_installed().await;
+            let pgmq_up = matches!(pgmq, Ok(true));
+            components.push(match pgmq {
+                Ok(true) => ComponentHealth::ok("pgmq"),
+                Ok(false) => ComponentHealth::failed("pgmq", "extension not installed"),
+                Err(e) => ComponentHealth::failed("pgmq", e.to_string()),
+            });
+
+            if pgmq_up {
+                for queue in &self.config.queues {
+                    let name = format!("queue:{}", queue.name);
+                    components.push(match self.db.queue_exists(&queue.name).await {
+                        Ok(true) => ComponentHealth::ok(name),
+                        Ok(false) => ComponentHealth::failed(name, "queue does not exist"),
+                        Err(e) => ComponentHealth::failed(name, e.to_string()),
+                    });
+                }
+            }
+        }
+
+        components.push(match self.registry().len() {
+            0 => ComponentHealth::failed("faculties", "no faculties loaded"),
+            n => ComponentHealth {
+                detail: Some(format!("{n} loaded")),
+                ..ComponentHealth::ok("faculties")
+            },
+        });
+
+        HealthReport::new(components)
+    }
+
+    /// The current faculty registry.
+    fn registry(&self) -> Arc<FacultyRegistry> {
+        Arc::clone(
+            &self
+                .registry
+                .read()
+                .expect("faculty registry lock poisoned"),
+        )
+    }
+
+    /// Re-read the faculty directory and swap in the new registry.
+    ///
+    /// In-flight foci keep the faculty config they started with; work
+    /// routed after the swap uses the new one. If the directory fails to
+    /// load or validate, the current registry stays in place.
+    pub fn reload_faculties(&self) -> Result<()> {
+        let reloaded = self.registry().reload()?;
+        reloaded
+            .validate()
+            .map_err(|errors| crate::faculty::config_error(&errors))?;
+        let reloaded = Arc::new(reloaded);
+        *self
+            .registry
+            .write()
+            .expect("faculty registry lock poisoned") = reloaded;
+        info!("faculty registry reloaded");
+        Ok(())
+    }
+
+    /// Remove directories under `focus_base_dir` that belong to no live
+    /// focus and were last modified more than `orphan_age` ago — leftovers
+    /// from a crash between focus creation and cleanup. Returns how many
+    /// were removed. Runs once at startup.
+    pub async fn sweep_orphaned_foci(&self) -> Result<u64> {
+        let mut entries = match tokio::fs::read_dir(&self.config.focus_base_dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut swept = 0;
+        while let Some(entry) = entries.next_entry().await? {
+            let meta = entry.metadata().await?;
+            if !meta.is_dir() {
+                continue;
+            }
+            let live = entry
+                .file_name()
+                .to_str()
+                .and_then(|name| Uuid::parse_str(name).ok())
+                .is_some_and(|id| {
+                    self.live_foci
+                        .lock()
+                        .expect("live foci lock poisoned")
+                        .contains(&id)
+                });
+            let age = meta.modified()?.elapsed().unwrap_or_default();
+            if live || age < self.config.orphan_age {
+                continue;
+            }
+            tokio::fs::remove_dir_all(entry.path()).await?;
+            swept += 1;
+        }
+        if swept > 0 {
+            info!(swept, "removed orphaned focus directories");
+        }
+        Ok(swept)
+    }
+
+    /// Seconds to hide unroutable work after its `reads`th read: the
+    /// visibility timeout doubled per earlier read, capped.
+    fn unroutable_backoff(&self, reads: u32) -> i32 {
+        let base = self.config.visibility_timeout.max(1) as u64;
+        let cap = self.config.unroutable_backoff_cap.as_secs();
+        let delay = base.saturating_mul(1 << (reads - 1).min(32)).min(cap);
+        delay.min(i32::MAX as u64) as i32
+    }
+
+    /// Whether `focus_base_dir` has grown past `focus_quota_bytes`.
+    async fn over_quota(&self) -> Result<bool> {
+        let Some(quota) = self.config.focus_quota_bytes else {
+            return Ok(false);
+        };
+        let used = dir_size(&self.config.focus_base_dir).await?;
+        if used > quota {
+            warn!(used, quota, "focus directory over quota, not spawning foci");
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    /// Signal the control plane to shut down. In-flight foci are drained
+    /// for up to [`ControlConfig::drain_timeout`].
     pub fn shutdown(&self) {
         self.shutdown.notify_one();
     }
 
+    /// Cancel the running focus for `id`: its hook is killed and the item
+    /// fails without retry. Returns false if no focus is running it here.
+    pub fn cancel_work(&self, id: WorkId) -> bool {
+        match self
+            .running
+            .lock()
+            .expect("running foci lock poisoned")
+            .get(&id)
+        {
+            Some(token) => {
+                info!(id = %id, "cancelling focus");
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Run the control plane loop until shutdown.
     pub async fn run(&self) -> Result<()> {
         // Ensure focus base dir exists
         tokio::fs::create_dir_all(&self.config.focus_base_dir).await?;
+        if let Err(e) = self.sweep_orphaned_foci().await {
+            warn!("orphaned focus sweep failed: {e}");
+        }
 
-        // Connect PgListener for NOTIFY
+        // Connect PgListener for NOTIFY, one channel per queue
         let mut listener = sqlx::postgres::PgListener::connect_with(self.db.pool()).await?;
-        listener.listen("work_ready").await?;
+        let channels: Vec<String> = self
+            .config
+            .queues
+            .iter()
+            .map(|q| format!("{}_ready", q.name))
+            .collect();
+        listener
+            .listen_all(channels.iter().map(String::as_str))
+            .await?;
 
         info!("control plane started, listening for work");
 
-        loop {
-            // Wait for: shutdown, notification, or poll timeout
-            let woke = tokio::select! {
-                _ = self.shutdown.notified() => {
-                    info!("control plane shutting down");
-                    return Ok(());
+        // Queue depth for the in-state gauge, refreshed on the poll interval,
+        // which is also the cadence for expiring work past its ttl
+        let state_counts = Arc::new(RwLock::new(HashMap::new()));
+        let _in_state = metrics::work_in_state(Arc::clone(&state_counts));
+        let refresh = tokio::spawn({
+            let db = Arc::clone(&self.db);
+            let interval = self.config.poll_interval;
+            async move {
+                loop {
+                    match db.count_by_state().await {
+                        Ok(counts) => {
+                            *state_counts
+                                .write()
+                                .expect("work state counts lock poisoned") = counts
+                        }
+                        Err(e) => warn!("count_by_state error: {e}"),
+                    }
+                    if let Err(e) = db.expire_overdue().await {
+                        warn!("expire_overdue error: {e}");
+                    }
+                    tokio::time::sleep(interval).await;
                 }
+            }
+        });
+
+        let mut foci = JoinSet::new();
+        loop {
+            // Fill free capacity before waiting (whether notified or polling)
+            self.fill_capacity(&mut foci).await;
+
+            // Wait for: shutdown, notification, a focus finishing (which
+            // frees capacity), or poll timeout
+            tokio::select! {
+                _ = self.shutdown.notified() => break,
                 notif = listener.recv() => {
                     match notif {
-                        Ok(n) => {
-                            info!(work_type = n.payload(), "notified of new work");
-                            true
-                        }
-                        Err(e) => {
-                            warn!("PgListener error: {e}, falling back to poll");
-                            false
-                        }
+                        Ok(n) => info!(work_type = n.payload(), "notified of new work"),
+                        Err(e) => warn!("PgListener error: {e}, falling back to poll"),
                     }
                 }
-                _ = tokio::time::sleep(self.config.poll_interval) => {
-                    false
+                Some(joined) = foci.join_next(), if !foci.is_empty() => {
+                    log_focus_exit(joined);
                 }
-            };
+                _ = tokio::time::sleep(self.config.poll_interval) => {}
+            }
+        }
+
+        refresh.abort();
+        self.drain(foci).await;
+        Ok(())
+    }
 
-            // Process available work (whether notified or polling)
-            let _ = woke; // both paths lead to process_work
-            if let Err(e) = self.process_work().await {
-                error!("process_work error: {e}");
+    /// Claim and spawn work until every queue is empty or at capacity.
+    async fn fill_capacity(&self, foci: &mut JoinSet<()>) {
+        // Nothing is claimed while over quota, so queued work stays visible
+        match self.over_quota().await {
+            Ok(false) => {}
+            Ok(true) => return,
+            Err(e) => {
+                error!("focus quota check error: {e}");
+                return;
+            }
+        }
+        for queue in &self.config.queues {
+            let queue_active = &self.queue_foci[&queue.name];
+            let queue_max = queue.max_concurrent.unwrap_or(usize::MAX);
+            loop {
+                let active = self.active_foci.load(Ordering::Relaxed);
+                let queue_free = queue_max.saturating_sub(queue_active.load(Ordering::Relaxed));
+                let capacity = self.max_concurrent.saturating_sub(active).min(queue_free);
+                if capacity == 0 {
+                    break;
+                }
+                match self.process_work(&queue.name, capacity, foci).await {
+                    Ok(true) => continue,
+                    Ok(false) => break,
+                    Err(e) => {
+                        error!(queue = %queue.name, "process_work error: {e}");
+                        break;
+                    }
+                }
             }
         }
     }
 
-    /// Try to claim and execute one work item.
-    async fn process_work(&self) -> Result<()> {
-        // Check capacity
-        if self.active_foci.load(Ordering::Relaxed) >= self.max_concurrent {
-            return Ok(());
+    /// Stop claiming and wait up to the drain timeout for in-flight foci.
+    /// Foci still running afterwards are cancelled and given a short grace
+    /// period to record the failure; any left are abandoned and their items
+    /// reappear after the visibility timeout.
+    async fn drain(&self, mut foci: JoinSet<()>) {
+        let active = foci.len();
+        info!(active, timeout = ?self.config.drain_timeout, "control plane draining");
+
+        let mut drained = 0;
+        let _ = tokio::time::timeout(self.config.drain_timeout, async {
+            while let Some(joined) = foci.join_next().await {
+                log_focus_exit(joined);
+                drained += 1;
+            }
+        })
+        .await;
+
+        if !foci.is_empty() {
+            warn!(remaining = foci.len(), "drain timed out, cancelling foci");
+            self.cancel.cancel();
+            let _ = tokio::time::timeout(CANCEL_GRACE, async {
+                while let Some(joined) = foci.join_next().await {
+                    log_focus_exit(joined);
+                    drained += 1;
+                }
+            })
+            .await;
         }
 
-        // Read from pgmq
-        let msg = self
+        let abandoned = foci.len();
+        if abandoned > 0 {
+            warn!(drained, abandoned, "drain timed out, abandoning foci");
+            foci.detach_all();
+        }
+        info!(drained, abandoned, "control plane shutting down");
+    }
+
+    /// Read up to `capacity` messages from `queue` in one go, then claim
+    /// each one's work item and spawn its focus onto `foci`. Every message
+    /// keeps its own visibility lease.
+    ///
+    /// Returns `false` once the queue is empty.
+    async fn process_work(
+        &self,
+        queue: &str,
+        capacity: usize,
+        foci: &mut JoinSet<()>,
+    ) -> Result<bool> {
+        let msgs = self
             .db
-            .read_from_queue("work", self.config.visibility_timeout)
+            .read_batch_from_queue(
+                queue,
+                self.config.visibility_timeout,
+                capacity.clamp(1, i32::MAX as usize) as i32,
+            )
             .await?;
+        if msgs.is_empty() {
+            return Ok(false);
+        }
 
-        let msg = match msg {
-            Some(m) => m,
-            None => return Ok(()), // queue empty
-        };
-
-        // Extract work_item_id from pgmq payload
-        let work_item_id = msg
-            .message
-            .get("work_item_id")
-            .and_then(|v| v.as_str())
-            .and_then(|s| Uuid::parse_str(s).ok())
-            .ok_or_else(|| Error::Other("bad pgmq payload: missing work_item_id".to_string()))?;
+        // One bad message shouldn't strand the rest of the batch until
+        // their visibility timeout runs out
+        for msg in msgs {
+            let msg_id = msg.msg_id;
+            if let Err(e) = self.process_message(queue, msg, foci).await {
+                error!(queue, msg_id, "process_work error: {e}");
+            }
+        }
+        Ok(true)
+    }
 
-        let work_id = WorkId(work_item_id);
+    /// Claim the work item behind one message and spawn its focus.
+    async fn process_message(
+        &self,
+        queue: &str,
+        msg: PgmqMessage,
+        foci: &mut JoinSet<()>,
+    ) -> Result<()> {
+        // A payload that can't be parsed would be re-read forever; move it
+        // aside so the rest of the queue keeps flowing
+        let payload = match WorkPayload::parse(&msg.message) {
+            Ok(payload) => payload,
+            Err(e) => {
+                warn!(queue, msg_id = msg.msg_id, "poison message: {e}");
+                self.db.poison_message(queue, &msg, &e.to_string()).await?;
+                return Ok(());
+            }
+        };
+        if payload.v > WORK_PAYLOAD_VERSION {
+            debug!(v = payload.v, "serving payload from a newer writer");
+        }
+        let work_item_id = payload.work_item_id;
+        let work_id = payload.work_id();
 
         // Fetch the full work item
         let item = self.db.get_work_item(work_id).await?;
+        match item.state {
+            State::Queued => {}
+            State::Paused => {
+                // The read hides the message for the visibility timeout;
+                // resume makes it visible again right away
+                debug!(id = %work_id, "work is paused, skipping");
+                return Ok(());
+            }
+            state if state.is_terminal() => {
+                // A leftover message for work that has already resolved
+                debug!(id = %work_id, %state, "work already resolved, archiving message");
+                self.db.archive_message(queue, msg.msg_id).await?;
+                return Ok(());
+            }
+            state => {
+                // Another replica holds the item; leave its message alone
+                debug!(id = %work_id, %state, "work in progress elsewhere, skipping");
+                return Ok(());
+            }
+        }
 
-        // Create a work execution span that wraps the entire lifecycle
-        let work_span = start_work_span(&item.faculty, &work_item_id);
+        // Expired work is dead-lettered rather than run
+        if item
+            .expires_at
+            .is_some_and(|at| at <= self.db.clock().now())
+        {
+            info!(id = %work_id, "work outlived its ttl, expiring");
+            self.db.expire_work(work_id).await?;
+            return Ok(());
+        }
+
+        // Work for types served by another deployment is left unclaimed;
+        // the read keeps its message hidden for the visibility timeout, so
+        // this replica moves on and another one picks it up after
+        if !self.config.accepts(&item.faculty) {
+            debug!(id = %work_id, faculty = %item.faculty, "work type not served here, skipping");
+            return Ok(());
+        }
 
-        // Everything from routing through retirement runs inside the work span
-        async {
+        // Create a work execution span that wraps the entire lifecycle,
+        // joining the submitter's trace when a context was propagated
+        let work_span = match item.trace_context {
+            Some(ref cx) => start_work_span_with_parent(&item.faculty, &work_item_id, cx),
+            None => start_work_span(&item.faculty, &work_item_id),
+        };
+
+        // Routing and claiming run inline, inside the work span
+        let faculty = async {
             // Dispatch to the faculty named in the work item
-            let faculty = match self.registry.get(&item.faculty) {
+            let faculty = match self.registry().get(&item.faculty) {
                 Some(f) => f.clone(),
                 None => {
-                    // No faculty registered with this name. Leave the message
-                    // in the queue — the visibility timeout will make it reappear.
-                    // The faculty may be registered later (e.g., during bootstrap).
+                    // No faculty registered with this name. The faculty may
+                    // be registered later (e.g., during bootstrap), so back
+                    // off and retry, dead-lettering once reads run out.
+                    metrics::work_unroutable()
+                        .add(1, &[KeyValue::new("faculty", item.faculty.clone())]);
+                    let reads = self.db.note_unroutable(work_id).await?;
+                    if reads >= self.config.unroutable_max_reads {
+                        warn!(
+                            faculty = %item.faculty,
+                            work_id = %work_item_id,
+                            reads,
+                            "no faculty registered, dead-lettering"
+                        );
+                        record_state_transition(&work_span, "queued", "dead");
+                        self.db
+                            .dead_letter(work_id, &format!("no faculty after {reads} attempts"))
+                            .await?;
+                        return Ok(None);
+                    }
+                    let delay = self.unroutable_backoff(reads);
                     warn!(
                         faculty = %item.faculty,
                         work_id = %work_item_id,
-                        "no faculty registered, skipping (will retry after visibility timeout)"
+                        reads,
+                        delay_secs = delay,
+                        "no faculty registered, skipping"
                     );
-                    metrics::work_unroutable()
-                        .add(1, &[KeyValue::new("faculty", item.faculty.clone())]);
-                    return Ok(());
+                    self.db.set_visibility(queue, msg.msg_id, delay).await?;
+                    return Ok(None);
                 }
             };
 
-            // Claim → Running
-            record_state_transition(&work_span, "queued", "claimed");
-            self.db
+            // While the faculty's circuit is open, new work is dead-lettered
+            // rather than piled onto a failing downstream
+            if let CircuitState::Open { remaining } = self.circuit_state(&item.faculty) {
+                warn!(
+                    faculty = %item.faculty,
+                    work_id = %work_item_id,
+                    remaining_secs = remaining.as_secs(),
+                    "circuit open, dead-lettering work"
+                );
+                record_state_transition(&work_span, "queued", "dead");
+                self.db.dead_letter(work_id, "circuit open").await?;
+                metrics::work_circuit_rejected()
+                    .add(1, &[KeyValue::new("faculty", item.faculty.clone())]);
+                return Ok(None);
+            }
+
+            // Late work still runs; the hook decides whether it's still
+            // useful. Count it so SLA misses can be alerted on.
+            if let Some(deadline) = item.deadline
+                && deadline < self.db.clock().now()
+            {
+                warn!(
+                    faculty = %item.faculty,
+                    work_id = %work_item_id,
+                    %deadline,
+                    "work picked up past its deadline"
+                );
+                metrics::work_overdue().add(1, &[KeyValue::new("faculty", item.faculty.clone())]);
+            }
+
+            // Claim it. Losing the claim to another replica is routine
+            // when several share a queue.
+            match self
+                .db
                 .transition_state(work_id, State::Queued, State::Claimed)
-                .await?;
-            record_state_transition(&work_span, "claimed", "running");
-            self.db
-                .transition_state(work_id, State::Claimed, State::Running)
-                .await?;
-
-            self.active_foci.fetch_add(1, Ordering::Relaxed);
-
-            // Create focus and run pipeline
-            let focus = Focus::create(&self.config.focus_base_dir, item).await?;
-            info!(
-                focus_id = %focus.id,
-                faculty = %faculty.name,
-                "focus spawned"
-            );
-            let result = focus.run(&faculty).await;
-
-            self.active_foci.fetch_sub(1, Ordering::Relaxed);
-
-            // Retire work item based on result
-            match result {
-                FocusResult::Completed {
-                    outcome_data,
-                    duration_ms,
-                } => {
-                    record_state_transition(&work_span, "running", "completed");
-                    info!(id = %work_id, duration_ms, "focus completed");
-                    self.db
-                        .complete_work(
-                            work_id,
-                            Outcome {
-                                success: true,
-                                data: Some(outcome_data),
-                                error: None,
-                                duration_ms,
-                            },
-                        )
-                        .await?;
-                    self.db.archive_message("work", msg.msg_id).await?;
+                .await
+            {
+                Ok(_) => record_state_transition(&work_span, "queued", "claimed"),
+                Err(Error::InvalidTransition { .. }) => {
+                    debug!(id = %work_id, "claimed by another replica, skipping");
+                    return Ok(None);
                 }
-                FocusResult::Failed {
-                    phase,
-                    error,
-                    duration_ms,
-                } => {
-                    record_state_transition(&work_span, "running", "failed");
-                    error!(id = %work_id, phase, %error, duration_ms, "focus failed");
-                    self.db
-                        .fail_work(work_id, &format!("{phase}: {error}"), duration_ms)
-                        .await?;
-                    // Leave message in queue — visibility timeout will make it reappear
-                    // for retry (v1: no recovery hook invocation)
+                Err(e) => return Err(e),
+            }
+
+            Ok::<_, Error>(Some(faculty))
+        }
+        .instrument(work_span.clone())
+        .await?;
+        let Some(faculty) = faculty else {
+            return Ok(());
+        };
+
+        // Execution runs as its own task so the loop can keep filling capacity
+        let active = ActiveFocus::enter(&[&self.active_foci, &self.queue_foci[queue]]);
+        let ctrl = self.clone();
+        let span = work_span.clone();
+        foci.spawn(
+            async move {
+                let _active = active;
+                if let Err(e) = ctrl.execute(item, faculty, msg.msg_id, &span).await {
+                    error!(id = %work_id, "focus error: {e}");
                 }
             }
+            .instrument(work_span),
+        );
+        Ok(())
+    }
+
+    /// Claimed → Running, once the item's focus exists.
+    async fn start_running(&self, work_id: WorkId, work_span: &Span) -> Result<()> {
+        record_state_transition(work_span, "claimed", "running");
+        self.db
+            .transition_state(work_id, State::Claimed, State::Running)
+            .await?;
+        Ok(())
+    }
+
+    /// Run a claimed item's focus and retire the item.
+    async fn execute(
+        &self,
+        item: WorkItem,
+        faculty: FacultyMeta,
+        msg_id: i64,
+        work_span: &Span,
+    ) -> Result<()> {
+        let work_id = item.id;
+        let queue = item.queue.clone();
 
-            // Cleanup focus directory
-            if let Err(e) = focus.cleanup().await {
-                warn!(focus_id = %focus.id, "cleanup error: {e}");
+        // Create focus and run pipeline
+        let cancel = self.cancel.child_token();
+        let focus = match Focus::create_with_max_params(
+            &self.config.focus_base_dir,
+            item,
+            self.config.max_params_bytes,
+        )
+        .await
+        {
+            Ok(focus) => focus,
+            // Rejected work won't be accepted on a retry either
+            Err(Error::InvalidState(reason)) => {
+                self.start_running(work_id, work_span).await?;
+                record_state_transition(work_span, "running", "dead");
+                error!(id = %work_id, %reason, "focus rejected");
+                self.db
+                    .fail_with_policy(work_id, &reason, 0, FailureClass::NonRetryable)
+                    .await?;
+                return Ok(());
+            }
+            // The focus never started, so this doesn't count as an attempt
+            Err(e) => {
+                record_state_transition(work_span, "claimed", "queued");
+                warn!(id = %work_id, "focus could not be created, requeueing: {e}");
+                self.db.requeue_claimed(work_id).await?;
+                return Err(e);
             }
+        }
+        .with_max_concurrent(self.max_concurrent)
+        .with_work_log(Arc::clone(&self.db))
+        .with_cancellation(cancel.clone());
+        if let Err(e) = self.start_running(work_id, work_span).await {
+            focus.cleanup().await.ok();
+            return Err(e);
+        }
+        self.running
+            .lock()
+            .expect("running foci lock poisoned")
+            .insert(work_id, cancel.clone());
+        self.live_foci
+            .lock()
+            .expect("live foci lock poisoned")
+            .insert(focus.id);
+        info!(
+            focus_id = %focus.id,
+            faculty = %faculty.name,
+            "focus spawned"
+        );
+        metrics::focus_spawned().add(1, &[KeyValue::new("faculty", faculty.name.clone())]);
+        let result = self.run_with_lease(&focus, &faculty, &queue, msg_id).await;
+        self.running
+            .lock()
+            .expect("running foci lock poisoned")
+            .remove(&work_id);
 
-            Ok(())
+        // Retire work item based on result
+        let outcome = match result {
+            FocusResult::Completed { .. } => "completed",
+            FocusResult::Failed { .. } => "failed",
+        };
+        match result {
+            FocusResult::Completed {
+                outcome_data,
+                duration_ms,
+            } => {
+                record_state_transition(work_span, "running", "completed");
+                info!(id = %work_id, duration_ms, "focus completed");
+                self.db
+                    .complete_work(
+                        work_id,
+                        Outcome {
+                            success: true,
+                            data: Some(outcome_data),
+                            error: None,
+                            duration_ms,
+                        },
+                    )
+                    .await?;
+                self.db.archive_message(&queue, msg_id).await?;
+            }
+            FocusResult::Failed {
+                phase,
+                error,
+                duration_ms,
+            } => {
+                record_state_transition(work_span, "running", "failed");
+                error!(id = %work_id, phase, %error, duration_ms, "focus failed");
+                // An explicit cancel is final; otherwise (including a
+                // shutdown cancel) retry on the visibility-timeout cadence
+                // until attempts run out (v1: no recovery hook invocation)
+                let retry = if cancel.is_cancelled() && !self.cancel.is_cancelled() {
+                    FailureClass::NonRetryable
+                } else {
+                    FailureClass::RetryAfter(std::time::Duration::from_secs(
+                        self.config.visibility_timeout.max(0) as u64,
+                    ))
+                };
+                let item = self
+                    .db
+                    .fail_with_policy(work_id, &format!("{phase}: {error}"), duration_ms, retry)
+                    .await?;
+                if item.state == State::Dead {
+                    record_state_transition(work_span, "failed", "dead");
+                    warn!(id = %work_id, attempts = item.attempts, "attempts exhausted, work is dead");
+                } else {
+                    record_state_transition(work_span, "failed", "queued");
+                }
+                if let Some(circuit) = &self.circuit
+                    && circuit.record_failure(&faculty.name)
+                {
+                    warn!(faculty = %faculty.name, "circuit opened after repeated failures");
+                }
+            }
         }
-        .instrument(work_span.clone())
-        .await
+
+        metrics::focus_completed().add(
+            1,
+            &[
+                KeyValue::new("faculty", faculty.name.clone()),
+                KeyValue::new("result", outcome),
+            ],
+        );
+
+        // Cleanup focus directory
+        if let Err(e) = focus.cleanup().await {
+            warn!(focus_id = %focus.id, "cleanup error: {e}");
+            let kind = match e {
+                Error::Io(ref io) => format!("{:?}", io.kind()),
+                _ => "other".to_string(),
+            };
+            metrics::focus_cleanup_failures().add(1, &[KeyValue::new("kind", kind)]);
+        }
+        self.live_foci
+            .lock()
+            .expect("live foci lock poisoned")
+            .remove(&focus.id);
+
+        Ok(())
+    }
+
+    /// Run a focus while renewing its message's lease every half
+    /// visibility timeout, so a long phase isn't picked up again by
+    /// another replica.
+    async fn run_with_lease(
+        &self,
+        focus: &Focus,
+        faculty: &FacultyMeta,
+        queue: &str,
+        msg_id: i64,
+    ) -> FocusResult {
+        let vt = self.config.visibility_timeout.max(1);
+        let period = std::time::Duration::from_millis(vt as u64 * 500);
+        let mut heartbeat = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+        let run = focus.run(faculty);
+        tokio::pin!(run);
+        loop {
+            tokio::select! {
+                result = &mut run => return result,
+                _ = heartbeat.tick() => {
+                    if let Err(e) = self.db.extend_visibility(queue, msg_id, vt).await {
+                        warn!(focus_id = %focus.id, "lease renewal failed: {e}");
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Counts a focus in the active counters (global and per-queue) for as
+/// long as it lives, so slots are released even if the focus task errors
+/// or panics.
+struct ActiveFocus(Vec<Arc<AtomicUsize>>);
+
+impl ActiveFocus {
+    fn enter(counters: &[&Arc<AtomicUsize>]) -> Self {
+        for c in counters {
+            c.fetch_add(1, Ordering::Relaxed);
+        }
+        Self(counters.iter().map(|c| Arc::clone(c)).collect())
+    }
+}
+
+impl Drop for ActiveFocus {
+    fn drop(&mut self) {
+        for c in &self.0 {
+            c.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Total size in bytes of the regular files under `dir`.
+async fn dir_size(dir: &Path) -> Result<u64> {
+    let mut total = 0;
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let mut entries = match tokio::fs::read_dir(&dir).await {
+            Ok(entries) => entries,
+            // A focus may be cleaned up mid-walk
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e.into()),
+        };
+        while let Some(entry) = entries.next_entry().await? {
+            let meta = match entry.metadata().await {
+                Ok(meta) => meta,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e.into()),
+            };
+            if meta.is_dir() {
+                pending.push(entry.path());
+            } else {
+                total += meta.len();
+            }
+        }
+    }
+    Ok(total)
+}
+
+/// Log a focus task that ended abnormally.
+fn log_focus_exit(joined: std::result::Result<(), tokio::task::JoinError>) {
+    if let Err(e) = joined {
+        error!("focus task failed: {e}");
     }
 }
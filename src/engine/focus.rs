@@ -1,11 +1,19 @@
 //! Focus lifecycle: create working directory, run hook pipeline, read outcome.
 
+use crate::db::Db;
 use crate::error::{Error, Result};
-use crate::faculty::FacultyMeta;
-use crate::model::work::WorkItem;
+use crate::faculty::{FacultyMeta, HookConfig, schema};
+use crate::model::work::{LogLevel, WorkId, WorkItem};
+use crate::telemetry::metrics;
+use crate::telemetry::work::{record_span_attributes, trace_env};
+use opentelemetry::KeyValue;
 use std::path::{Path, PathBuf};
-use std::time::Instant;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
 use tokio::process::Command;
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
@@ -22,23 +30,162 @@ pub enum FocusResult {
     },
 }
 
+/// Largest serialized `params` a focus accepts by default (1 MiB).
+pub const DEFAULT_MAX_PARAMS_BYTES: usize = 1024 * 1024;
+
+/// Environment variables describing a work item, passed to every hook.
+///
+/// Besides the identity variables, `ANIMUS_DEDUP_KEY` (when set) and
+/// `ANIMUS_PRIORITY` are exported, and each top-level scalar in `params`
+/// becomes `ANIMUS_PARAM_<KEY>` (key uppercased, non-alphanumerics as `_`)
+/// so shell hooks can skip parsing `work.json`. Objects, arrays, and nulls
+/// are left to `work.json`.
+///
+/// Focus- and phase-specific variables (`ANIMUS_FOCUS_DIR`, `ANIMUS_PHASE`)
+/// are added by the focus itself.
+pub fn work_env(item: &WorkItem) -> Vec<(String, String)> {
+    let mut env = vec![
+        ("ANIMUS_FACULTY".to_string(), item.faculty.clone()),
+        ("ANIMUS_WORK_ID".to_string(), item.id.0.to_string()),
+        ("ANIMUS_PRIORITY".to_string(), item.priority.to_string()),
+    ];
+    if let Some(ref key) = item.dedup_key {
+        env.push(("ANIMUS_DEDUP_KEY".to_string(), key.clone()));
+    }
+    if let Some(params) = item.params.as_object() {
+        for (key, value) in params {
+            let value = match value {
+                serde_json::Value::String(s) => s.clone(),
+                serde_json::Value::Number(n) => n.to_string(),
+                serde_json::Value::Bool(b) => b.to_string(),
+                _ => continue,
+            };
+            env.push((format!("ANIMUS_PARAM_{}", env_key(key)), value));
+        }
+    }
+    env
+}
+
+/// Uppercase a params key into an env var name fragment.
+fn env_key(key: &str) -> String {
+    key.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Replace each `${VAR}` in `value` with the process's `VAR`, or nothing
+/// if it is unset. A `${` with no closing brace is left as is.
+fn interpolate_env(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        let Some(len) = rest[start + 2..].find('}') else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        let name = &rest[start + 2..start + 2 + len];
+        out.push_str(&std::env::var(name).unwrap_or_default());
+        rest = &rest[start + 3 + len..];
+    }
+    out.push_str(rest);
+    out
+}
+
 /// A focus is a temporary working context for executing a work item.
 pub struct Focus {
     pub id: Uuid,
     pub dir: PathBuf,
     pub work_item: WorkItem,
+    /// Engine-wide foci limit, surfaced to hooks so they can size their
+    /// own pools. Defaults to 1.
+    pub max_concurrent: usize,
+    /// Where captured hook output goes. None = hooks inherit the engine's
+    /// stdout and stderr.
+    work_log: Option<Arc<Db>>,
+    /// Cancelling stops the pipeline: the running hook is killed and the
+    /// focus fails with "cancelled".
+    cancel: CancellationToken,
 }
 
 impl Focus {
-    /// Create a new focus: make the directory, write work.json.
+    /// Create a new focus: make the directory, write work.json. Params are
+    /// capped at [`DEFAULT_MAX_PARAMS_BYTES`].
     pub async fn create(base_dir: &Path, work_item: WorkItem) -> Result<Self> {
+        Self::create_with_max_params(base_dir, work_item, DEFAULT_MAX_PARAMS_BYTES).await
+    }
+
+    /// Create a new focus, rejecting work whose serialized params exceed
+    /// `max_params_bytes`.
+    ///
+    /// Everything that can be checked up front is: the params size, that
+    /// `base_dir` is writable, and that the focus directory is new. If
+    /// writing work.json then fails, the directory is removed.
+    pub async fn create_with_max_params(
+        base_dir: &Path,
+        work_item: WorkItem,
+        max_params_bytes: usize,
+    ) -> Result<Self> {
+        let params_bytes = serde_json::to_vec(&work_item.params)
+            .map_err(|e| Error::Other(format!("serialize params: {e}")))?
+            .len();
+        if params_bytes > max_params_bytes {
+            return Err(Error::InvalidState(format!(
+                "work item {} params are {params_bytes} bytes, over the {max_params_bytes} byte limit",
+                work_item.id
+            )));
+        }
+
+        let unusable = |e: std::io::Error| {
+            Error::Config(format!(
+                "focus base dir {} is not usable: {e}",
+                base_dir.display()
+            ))
+        };
+        tokio::fs::create_dir_all(base_dir)
+            .await
+            .map_err(unusable)?;
+        if tokio::fs::metadata(base_dir)
+            .await
+            .map_err(unusable)?
+            .permissions()
+            .readonly()
+        {
+            return Err(Error::Config(format!(
+                "focus base dir {} is not writable",
+                base_dir.display()
+            )));
+        }
+
         let id = Uuid::new_v4();
         let dir = base_dir.join(id.to_string());
-        tokio::fs::create_dir_all(&dir).await?;
+        // create_dir, not create_dir_all: an existing directory is a
+        // collision, not something to reuse
+        tokio::fs::create_dir(&dir)
+            .await
+            .map_err(|e| match e.kind() {
+                std::io::ErrorKind::AlreadyExists => {
+                    Error::InvalidState(format!("focus directory {} already exists", dir.display()))
+                }
+                _ => unusable(e),
+            })?;
 
-        let work_json = serde_json::to_string_pretty(&work_item)
-            .map_err(|e| Error::Other(format!("serialize work item: {e}")))?;
-        tokio::fs::write(dir.join("work.json"), work_json).await?;
+        let written = async {
+            let work_json = serde_json::to_string_pretty(&work_item)
+                .map_err(|e| Error::Other(format!("serialize work item: {e}")))?;
+            tokio::fs::write(dir.join("work.json"), work_json).await?;
+            Ok::<_, Error>(())
+        }
+        .await;
+        if let Err(e) = written {
+            let _ = tokio::fs::remove_dir_all(&dir).await;
+            return Err(e);
+        }
 
         debug!(
             focus_id = %id,
@@ -47,37 +194,130 @@ impl Focus {
             "focus created"
         );
 
-        Ok(Self { id, dir, work_item })
+        Ok(Self {
+            id,
+            dir,
+            work_item,
+            max_concurrent: 1,
+            work_log: None,
+            cancel: CancellationToken::new(),
+        })
+    }
+
+    /// Set the engine-wide concurrency limit reported to hooks.
+    pub fn with_max_concurrent(mut self, max_concurrent: usize) -> Self {
+        self.max_concurrent = max_concurrent;
+        self
+    }
+
+    /// Capture hook stdout and stderr line by line into the work item's
+    /// durable log (see [`Db::append_log`]): stdout at info, stderr at
+    /// warn, each line prefixed with its phase.
+    pub fn with_work_log(mut self, db: Arc<Db>) -> Self {
+        self.work_log = Some(db);
+        self
+    }
+
+    /// Stop the pipeline when `token` is cancelled. The running hook is
+    /// killed, later phases are skipped, and the focus fails with error
+    /// "cancelled" in the interrupted phase.
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancel = token;
+        self
+    }
+
+    /// Concurrency hints for hooks: `ANIMUS_MAX_CONCURRENT` is the engine
+    /// limit, `ANIMUS_FACULTY_MAX_CONCURRENT` how many foci of this faculty
+    /// may run at once (1 unless the faculty is concurrent).
+    fn concurrency_env(&self, faculty: &FacultyMeta) -> Vec<(String, String)> {
+        let faculty_max = if faculty.concurrent {
+            self.max_concurrent
+        } else {
+            1
+        };
+        vec![
+            (
+                "ANIMUS_MAX_CONCURRENT".to_string(),
+                self.max_concurrent.to_string(),
+            ),
+            (
+                "ANIMUS_FACULTY_MAX_CONCURRENT".to_string(),
+                faculty_max.to_string(),
+            ),
+        ]
     }
 
     /// Run the orient → engage → consolidate pipeline.
     pub async fn run(&self, faculty: &FacultyMeta) -> FocusResult {
+        let result = self.run_pipeline(faculty).await;
+        let (duration_ms, outcome) = match &result {
+            FocusResult::Completed { duration_ms, .. } => (*duration_ms, "completed"),
+            FocusResult::Failed { duration_ms, .. } => (*duration_ms, "failed"),
+        };
+        metrics::operation_duration_ms().record(
+            duration_ms as f64,
+            &[
+                KeyValue::new("operation", "focus.run"),
+                KeyValue::new("faculty", faculty.name.clone()),
+                KeyValue::new("result", outcome),
+            ],
+        );
+        result
+    }
+
+    async fn run_pipeline(&self, faculty: &FacultyMeta) -> FocusResult {
         let start = Instant::now();
 
         // Build phase list — orient and consolidate are optional
-        let mut phases: Vec<(&str, &PathBuf)> = Vec::new();
+        let mut phases: Vec<(&str, &HookConfig)> = Vec::new();
         if let Some(ref orient) = faculty.orient {
-            phases.push(("orient", &orient.command));
+            phases.push(("orient", orient));
         }
-        phases.push(("engage", &faculty.engage.command));
+        phases.push(("engage", &faculty.engage));
         if let Some(ref consolidate) = faculty.consolidate {
-            phases.push(("consolidate", &consolidate.command));
+            phases.push(("consolidate", consolidate));
         }
 
-        for (phase, command) in &phases {
+        let concurrency = self.concurrency_env(faculty);
+        // Phase outputs so far, keyed by phase, mirrored to pipeline.json
+        let mut pipeline = serde_json::Map::new();
+        let mut prev_out: Option<PathBuf> = None;
+        for (phase, hook) in &phases {
+            if self.cancel.is_cancelled() {
+                warn!(focus_id = %self.id, phase, "focus cancelled");
+                return FocusResult::Failed {
+                    phase: phase.to_string(),
+                    error: "cancelled".to_string(),
+                    duration_ms: start.elapsed().as_millis() as u64,
+                };
+            }
             let phase_start = Instant::now();
-            match self.run_hook(phase, command).await {
+            let mut env = concurrency.clone();
+            if let Some(ref prev) = prev_out {
+                env.push(("ANIMUS_PREV_OUT".to_string(), prev.display().to_string()));
+            }
+            let hook = self.run_hook(phase, hook, &env).await;
+            let phase_ms = phase_start.elapsed().as_millis() as u64;
+            metrics::operation_duration_ms().record(
+                phase_ms as f64,
+                &[
+                    KeyValue::new("operation", "focus.phase"),
+                    KeyValue::new("phase", phase.to_string()),
+                    KeyValue::new("faculty", faculty.name.clone()),
+                    KeyValue::new("result", if hook.is_ok() { "ok" } else { "error" }),
+                ],
+            );
+            match hook {
                 Ok(()) => {
-                    let phase_ms = phase_start.elapsed().as_millis() as u64;
                     info!(
                         focus_id = %self.id,
                         phase,
                         duration_ms = phase_ms,
                         "phase completed"
                     );
+                    prev_out = self.record_phase_output(phase, &mut pipeline).await;
                 }
                 Err(e) => {
-                    let phase_ms = phase_start.elapsed().as_millis() as u64;
                     warn!(
                         focus_id = %self.id,
                         phase,
@@ -94,34 +334,90 @@ impl Focus {
             }
         }
 
-        // Read outcome data — prefer consolidate-out.json, fall back to engage-out.json
-        let outcome_path = if self.dir.join("consolidate-out.json").exists() {
-            self.dir.join("consolidate-out.json")
-        } else {
-            self.dir.join("engage-out.json")
+        // Read outcome data — the faculty's outcome_file if configured,
+        // else prefer consolidate-out.json, falling back to engage-out.json
+        let (phase, outcome_file) = match faculty.outcome_file {
+            Some(ref file) => {
+                let last = phases.last().map_or("engage", |(phase, _)| *phase);
+                (last, file.clone())
+            }
+            None if self.dir.join("consolidate-out.json").exists() => {
+                ("consolidate", PathBuf::from("consolidate-out.json"))
+            }
+            None => ("consolidate", PathBuf::from("engage-out.json")),
         };
-        match tokio::fs::read_to_string(&outcome_path).await {
+        let failed = |error: String| FocusResult::Failed {
+            phase: phase.to_string(),
+            error,
+            duration_ms: start.elapsed().as_millis() as u64,
+        };
+        match tokio::fs::read_to_string(self.dir.join(&outcome_file)).await {
             Ok(content) => match serde_json::from_str(&content) {
-                Ok(data) => FocusResult::Completed {
-                    outcome_data: data,
-                    duration_ms: start.elapsed().as_millis() as u64,
-                },
-                Err(e) => FocusResult::Failed {
-                    phase: "consolidate".to_string(),
-                    error: format!("bad consolidate-out.json: {e}"),
-                    duration_ms: start.elapsed().as_millis() as u64,
-                },
-            },
-            Err(e) => FocusResult::Failed {
-                phase: "consolidate".to_string(),
-                error: format!("missing consolidate-out.json: {e}"),
-                duration_ms: start.elapsed().as_millis() as u64,
+                Ok(data) => {
+                    if let Some(ref schema) = faculty.outcome_schema
+                        && let Err(violations) = schema::validate(schema, &data)
+                    {
+                        return failed(format!(
+                            "outcome violates schema: {}",
+                            violations.join("; ")
+                        ));
+                    }
+                    FocusResult::Completed {
+                        outcome_data: data,
+                        duration_ms: start.elapsed().as_millis() as u64,
+                    }
+                }
+                Err(e) => failed(format!("bad {}: {e}", outcome_file.display())),
             },
+            Err(e) if faculty.outcome_file.is_some() => failed(format!(
+                "outcome file {} was not produced: {e}",
+                outcome_file.display()
+            )),
+            Err(e) => failed(format!("missing consolidate-out.json: {e}")),
         }
     }
 
-    /// Run a single hook command.
-    async fn run_hook(&self, phase: &str, command: &Path) -> Result<()> {
+    /// Fold `<phase>-out.json` into `pipeline` and rewrite `pipeline.json`.
+    /// A top-level `span_attributes` object in the output is recorded on
+    /// the work span.
+    ///
+    /// Returns the output path for the next phase's `ANIMUS_PREV_OUT`, or
+    /// None if the phase wrote nothing. Output that isn't valid JSON is
+    /// still passed on but left out of `pipeline.json`.
+    async fn record_phase_output(
+        &self,
+        phase: &str,
+        pipeline: &mut serde_json::Map<String, serde_json::Value>,
+    ) -> Option<PathBuf> {
+        let out = self.dir.join(format!("{phase}-out.json"));
+        let content = tokio::fs::read_to_string(&out).await.ok()?;
+        match serde_json::from_str::<serde_json::Value>(&content) {
+            Ok(data) => {
+                if let Some(attributes) = data.get("span_attributes").and_then(|a| a.as_object()) {
+                    record_span_attributes(&tracing::Span::current(), attributes);
+                }
+                pipeline.insert(phase.to_string(), data);
+                let json = serde_json::Value::Object(pipeline.clone()).to_string();
+                if let Err(e) = tokio::fs::write(self.dir.join("pipeline.json"), json).await {
+                    warn!(focus_id = %self.id, phase, "write pipeline.json: {e}");
+                }
+            }
+            Err(e) => warn!(focus_id = %self.id, phase, "{phase}-out.json is not JSON: {e}"),
+        }
+        Some(out)
+    }
+
+    /// Run a single hook command with `extra_env` on top of the work env.
+    /// `TRACEPARENT` carries the work span's trace context so the hook can
+    /// continue the trace.
+    async fn run_hook(
+        &self,
+        phase: &str,
+        hook: &HookConfig,
+        extra_env: &[(String, String)],
+    ) -> Result<()> {
+        let command = hook.command.as_path();
+        let timeout = hook.timeout_secs.map(Duration::from_secs);
         // Resolve relative command paths against the process CWD (project root),
         // not the focus dir. Command::new + current_dir resolves relative paths
         // after chdir, which would look in the focus dir instead.
@@ -138,14 +434,77 @@ impl Focus {
             "running hook"
         );
 
-        let status = Command::new(&abs_command)
+        let mut command = Command::new(&abs_command);
+        command
+            .args(hook.args.iter().map(|arg| interpolate_env(arg)))
             .current_dir(&self.dir)
+            .envs(
+                hook.env
+                    .iter()
+                    .map(|(key, value)| (key, interpolate_env(value))),
+            )
+            .envs(work_env(&self.work_item))
+            .envs(extra_env.iter().cloned())
+            .envs(trace_env())
             .env("ANIMUS_FOCUS_DIR", &self.dir)
-            .env("ANIMUS_FACULTY", &self.work_item.faculty)
-            .env("ANIMUS_WORK_ID", self.work_item.id.0.to_string())
             .env("ANIMUS_PHASE", phase)
-            .status()
-            .await?;
+            .kill_on_drop(true);
+        apply_limits(&mut command, hook);
+        if self.work_log.is_some() {
+            command.stdout(Stdio::piped()).stderr(Stdio::piped());
+        }
+        let mut child = command.spawn()?;
+
+        let mut capture = Vec::new();
+        if let Some(ref db) = self.work_log {
+            let id = self.work_item.id;
+            if let Some(stdout) = child.stdout.take() {
+                capture.push(tokio::spawn(log_lines(
+                    Arc::clone(db),
+                    id,
+                    phase.to_string(),
+                    LogLevel::Info,
+                    stdout,
+                )));
+            }
+            if let Some(stderr) = child.stderr.take() {
+                capture.push(tokio::spawn(log_lines(
+                    Arc::clone(db),
+                    id,
+                    phase.to_string(),
+                    LogLevel::Warn,
+                    stderr,
+                )));
+            }
+        }
+
+        let wait = async {
+            match timeout {
+                Some(after) => match tokio::time::timeout(after, child.wait()).await {
+                    Ok(status) => status.map_err(Error::from),
+                    Err(_) => Err(Error::Timeout {
+                        phase: phase.to_string(),
+                        after,
+                    }),
+                },
+                None => child.wait().await.map_err(Error::from),
+            }
+        };
+        let status = tokio::select! {
+            status = wait => status,
+            _ = self.cancel.cancelled() => Err(Error::Other("cancelled".to_string())),
+        };
+        if status.is_err() {
+            // kill_on_drop would reap it too; kill now so the phase
+            // doesn't outlive its report
+            let _ = child.kill().await;
+        }
+        // Let the last lines land before the phase is reported. A
+        // background process still holding the pipes is not waited for.
+        for task in capture {
+            let _ = tokio::time::timeout(OUTPUT_DRAIN_TIMEOUT, task).await;
+        }
+        let status = status?;
 
         if status.success() {
             Ok(())
@@ -164,3 +523,65 @@ impl Focus {
         Ok(())
     }
 }
+
+/// Apply the hook's resource limits to its process before exec.
+#[cfg(target_os = "linux")]
+fn apply_limits(command: &mut Command, hook: &HookConfig) {
+    let limits: Vec<_> = [
+        (
+            libc::RLIMIT_AS,
+            hook.memory_mb.map(|mb| mb.saturating_mul(1024 * 1024)),
+        ),
+        (libc::RLIMIT_CPU, hook.cpu_time_secs),
+        (libc::RLIMIT_NPROC, hook.nproc),
+    ]
+    .into_iter()
+    .filter_map(|(resource, value)| Some((resource, value? as libc::rlim_t)))
+    .collect();
+    if limits.is_empty() {
+        return;
+    }
+    // SAFETY: the closure runs in the forked child before exec and only
+    // calls setrlimit, which is async-signal-safe, on data it owns.
+    unsafe {
+        command.pre_exec(move || {
+            for &(resource, value) in &limits {
+                let limit = libc::rlimit {
+                    rlim_cur: value,
+                    rlim_max: value,
+                };
+                if libc::setrlimit(resource, &limit) != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+            }
+            Ok(())
+        });
+    }
+}
+
+/// Resource limits are Linux-only; elsewhere hooks run uncapped.
+#[cfg(not(target_os = "linux"))]
+fn apply_limits(_command: &mut Command, hook: &HookConfig) {
+    if hook.memory_mb.is_some() || hook.cpu_time_secs.is_some() || hook.nproc.is_some() {
+        debug!(command = %hook.command.display(), "hook resource limits unsupported here, ignoring");
+    }
+}
+
+/// How long a finished hook's captured output may take to drain.
+const OUTPUT_DRAIN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Append each line of a hook's output stream to the work log.
+async fn log_lines(
+    db: Arc<Db>,
+    id: WorkId,
+    phase: String,
+    level: LogLevel,
+    stream: impl AsyncRead + Unpin,
+) {
+    let mut lines = BufReader::new(stream).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        if let Err(e) = db.append_log(id, level, &format!("{phase}: {line}")).await {
+            warn!(work_id = %id, phase, "work log append failed: {e}");
+        }
+    }
+}
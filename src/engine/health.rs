@@ -0,0 +1,55 @@
+//! Readiness report: one rollup over the database, pgmq, migrations, the
+//! consumed queues, and the faculty registry.
+
+use serde::Serialize;
+
+/// Result of checking one component.
+#[derive(Debug, Clone, Serialize)]
+pub struct ComponentHealth {
+    pub name: String,
+    pub ok: bool,
+    /// Why the check failed, or a short summary when it passed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl ComponentHealth {
+    pub fn ok(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ok: true,
+            detail: None,
+        }
+    }
+
+    pub fn failed(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ok: false,
+            detail: Some(detail.into()),
+        }
+    }
+}
+
+/// Per-component checks plus the rollup. Serializes to the JSON served by
+/// a readiness endpoint.
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthReport {
+    /// True when every component passed.
+    pub ready: bool,
+    pub components: Vec<ComponentHealth>,
+}
+
+impl HealthReport {
+    pub fn new(components: Vec<ComponentHealth>) -> Self {
+        Self {
+            ready: components.iter().all(|c| c.ok),
+            components,
+        }
+    }
+
+    /// The check named `name`, if it was run.
+    pub fn component(&self, name: &str) -> Option<&ComponentHealth> {
+        self.components.iter().find(|c| c.name == name)
+    }
+}
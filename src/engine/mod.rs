@@ -1,7 +1,11 @@
 //! Control plane engine: queue watching, focus lifecycle, work retirement.
 
+pub mod circuit;
 pub mod control;
 pub mod focus;
+pub mod health;
 
-pub use control::{ControlConfig, ControlPlane};
-pub use focus::Focus;
+pub use circuit::{CircuitBreaker, CircuitConfig, CircuitState};
+pub use control::{ControlConfig, ControlPlane, QueueConfig};
+pub use focus::{Focus, work_env};
+pub use health::{ComponentHealth, HealthReport};
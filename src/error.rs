@@ -1,14 +1,23 @@
 //! Error types for animus-rs.
 
+use crate::model::work::WorkId;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum Error {
-    #[error("not found: {0}")]
-    NotFound(String),
+    #[error("not found: {what}")]
+    NotFound {
+        what: String,
+        /// Set when the missing thing is a work item.
+        work_id: Option<WorkId>,
+    },
 
     #[error("invalid state transition: {from} -> {to}")]
-    InvalidTransition { from: String, to: String },
+    InvalidTransition {
+        from: String,
+        to: String,
+        work_id: Option<WorkId>,
+    },
 
     #[error("database error: {0}")]
     Database(#[from] sqlx::Error),
@@ -19,6 +28,12 @@ pub enum Error {
     #[error("configuration error: {0}")]
     Config(String),
 
+    #[error("{phase} timed out after {after:?}")]
+    Timeout {
+        phase: String,
+        after: std::time::Duration,
+    },
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
@@ -26,4 +41,30 @@ pub enum Error {
     Other(String),
 }
 
+impl Error {
+    /// A missing work item.
+    pub fn work_not_found(id: WorkId) -> Self {
+        Error::NotFound {
+            what: format!("work item {id}"),
+            work_id: Some(id),
+        }
+    }
+
+    /// Something other than a work item is missing.
+    pub fn not_found(what: impl Into<String>) -> Self {
+        Error::NotFound {
+            what: what.into(),
+            work_id: None,
+        }
+    }
+
+    /// The work item this error is about, if any.
+    pub fn work_id(&self) -> Option<WorkId> {
+        match self {
+            Error::NotFound { work_id, .. } | Error::InvalidTransition { work_id, .. } => *work_id,
+            _ => None,
+        }
+    }
+}
+
 pub type Result<T> = std::result::Result<T, Error>;
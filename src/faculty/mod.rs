@@ -3,9 +3,13 @@
 //! A faculty is a pluggable cognitive specialization. The work item specifies
 //! which faculty handles it directly — no routing table needed.
 
+pub mod schema;
+
 use crate::error::{Error, Result};
 use serde::Deserialize;
 use std::collections::HashMap;
+#[cfg(unix)]
+use std::os::unix::fs::PermissionsExt;
 use std::path::{Path, PathBuf};
 
 /// Top-level TOML wrapper.
@@ -26,12 +30,54 @@ pub struct FacultyMeta {
     pub engage: HookConfig,
     pub consolidate: Option<HookConfig>,
     pub recover: RecoverConfig,
+    /// File, relative to the focus directory, holding the outcome once the
+    /// pipeline finishes. None = `consolidate-out.json` if present, else
+    /// `engage-out.json`.
+    #[serde(default)]
+    pub outcome_file: Option<PathBuf>,
+    /// JSON Schema the outcome must satisfy, written inline as a TOML
+    /// table (`[faculty.outcome_schema]`). Outcomes that don't match fail
+    /// the focus instead of completing. None = any JSON. See [`schema`]
+    /// for the supported keywords.
+    #[serde(default)]
+    pub outcome_schema: Option<serde_json::Value>,
 }
 
-/// Configuration for a phase hook — just a path to an executable.
-#[derive(Debug, Clone, Deserialize)]
+/// Configuration for a phase hook — a path to an executable with fixed
+/// arguments and environment, optionally with time and resource limits.
+///
+/// `${VAR}` in `args` and `env` values is replaced with the engine
+/// process's `VAR` (empty if unset), so one hook binary can be pointed at
+/// different settings per faculty.
+///
+/// The resource limits are applied with `setrlimit` in the hook process
+/// before it execs, and are only enforced on Linux; elsewhere they are
+/// ignored.
+#[derive(Debug, Clone, Default, Deserialize)]
 pub struct HookConfig {
     pub command: PathBuf,
+    /// Arguments passed to `command`.
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Extra environment for the hook. The engine's `ANIMUS_*` variables
+    /// take precedence.
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    /// Kill the hook and fail the phase after this many seconds.
+    /// None = no limit.
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+    /// Cap on the hook's address space (`RLIMIT_AS`), in MiB.
+    #[serde(default)]
+    pub memory_mb: Option<u64>,
+    /// Cap on the hook's CPU time (`RLIMIT_CPU`); the kernel kills it
+    /// with SIGXCPU past this.
+    #[serde(default)]
+    pub cpu_time_secs: Option<u64>,
+    /// Cap on processes for the hook's user (`RLIMIT_NPROC`). Counts every
+    /// process the user owns, not just the hook's children.
+    #[serde(default)]
+    pub nproc: Option<u64>,
 }
 
 /// Recovery hook with retry limit.
@@ -41,9 +87,55 @@ pub struct RecoverConfig {
     pub max_attempts: u32,
 }
 
+/// A problem found while loading or validating faculty configs.
+#[derive(Debug, thiserror::Error)]
+pub enum FacultyError {
+    #[error("bad faculty config {}: {message}", path.display())]
+    Parse { path: PathBuf, message: String },
+
+    #[error("faculty {name} defined in both {} and {}", first.display(), second.display())]
+    DuplicateName {
+        name: String,
+        first: PathBuf,
+        second: PathBuf,
+    },
+
+    #[error("faculty {faculty}: {hook} command {} does not exist", command.display())]
+    MissingCommand {
+        faculty: String,
+        hook: String,
+        command: PathBuf,
+    },
+
+    #[error("faculty {faculty}: {hook} command {} is not executable", command.display())]
+    NotExecutable {
+        faculty: String,
+        hook: String,
+        command: PathBuf,
+    },
+
+    #[error("faculty {faculty}: outcome_file {} must be a relative path inside the focus directory", path.display())]
+    BadOutcomeFile { faculty: String, path: PathBuf },
+
+    #[error("faculty {faculty}: outcome_schema must be a table")]
+    BadOutcomeSchema { faculty: String },
+}
+
+/// Collapse a list of faculty problems into one config error.
+pub(crate) fn config_error(errors: &[FacultyError]) -> Error {
+    let report: Vec<String> = errors.iter().map(|e| format!("  - {e}")).collect();
+    Error::Config(format!(
+        "{} faculty config error(s):\n{}",
+        errors.len(),
+        report.join("\n")
+    ))
+}
+
 /// Registry of loaded faculties, indexed by name.
 pub struct FacultyRegistry {
     faculties: HashMap<String, FacultyMeta>,
+    /// Directory this registry was loaded from, for reloads.
+    dir: Option<PathBuf>,
 }
 
 impl FacultyRegistry {
@@ -51,35 +143,173 @@ impl FacultyRegistry {
     pub fn empty() -> Self {
         Self {
             faculties: HashMap::new(),
+            dir: None,
         }
     }
 
     /// Load all `.toml` files from a directory and build the registry.
+    ///
+    /// Every file is parsed before giving up, so a single `Config` error
+    /// reports all unparseable files and duplicate faculty names at once.
     pub fn load_from_dir(dir: &Path) -> Result<Self> {
         let mut faculties = HashMap::new();
+        let mut sources: HashMap<String, PathBuf> = HashMap::new();
+        let mut errors = Vec::new();
 
         let entries = std::fs::read_dir(dir).map_err(|e| {
             Error::Config(format!("cannot read faculty dir {}: {e}", dir.display()))
         })?;
 
+        let mut paths = Vec::new();
         for entry in entries {
-            let entry = entry?;
-            let path = entry.path();
+            let path = entry?.path();
             if path.extension().is_some_and(|ext| ext == "toml") {
-                let content = std::fs::read_to_string(&path)?;
-                let config: FacultyConfig = toml::from_str(&content).map_err(|e| {
-                    Error::Config(format!("bad faculty config {}: {e}", path.display()))
-                })?;
-                let meta = config.faculty;
-                faculties.insert(meta.name.clone(), meta);
+                paths.push(path);
             }
         }
+        // Sorted so duplicate reports are stable
+        paths.sort();
 
-        Ok(Self { faculties })
+        for path in paths {
+            let content = std::fs::read_to_string(&path)?;
+            let config: FacultyConfig = match toml::from_str(&content) {
+                Ok(config) => config,
+                Err(e) => {
+                    errors.push(FacultyError::Parse {
+                        path,
+                        message: e.to_string(),
+                    });
+                    continue;
+                }
+            };
+            let meta = config.faculty;
+            if let Some(first) = sources.get(&meta.name) {
+                errors.push(FacultyError::DuplicateName {
+                    name: meta.name,
+                    first: first.clone(),
+                    second: path,
+                });
+                continue;
+            }
+            sources.insert(meta.name.clone(), path);
+            faculties.insert(meta.name.clone(), meta);
+        }
+
+        if !errors.is_empty() {
+            return Err(config_error(&errors));
+        }
+
+        Ok(Self {
+            faculties,
+            dir: Some(dir.to_path_buf()),
+        })
+    }
+
+    /// Check every hook command exists and is executable, that any
+    /// `outcome_file` stays inside the focus directory, and that any
+    /// `outcome_schema` is a schema object.
+    ///
+    /// Relative commands are resolved against the process CWD, as when a
+    /// focus runs them. Returns all problems, not just the first.
+    pub fn validate(&self) -> std::result::Result<(), Vec<FacultyError>> {
+        let mut names: Vec<&String> = self.faculties.keys().collect();
+        names.sort();
+
+        let mut errors = Vec::new();
+        for name in names {
+            let meta = &self.faculties[name];
+            let mut hooks = vec![("engage", &meta.engage.command)];
+            if let Some(ref orient) = meta.orient {
+                hooks.insert(0, ("orient", &orient.command));
+            }
+            if let Some(ref consolidate) = meta.consolidate {
+                hooks.push(("consolidate", &consolidate.command));
+            }
+            hooks.push(("recover", &meta.recover.command));
+
+            if let Some(ref path) = meta.outcome_file
+                && !path
+                    .components()
+                    .all(|c| matches!(c, std::path::Component::Normal(_)))
+            {
+                errors.push(FacultyError::BadOutcomeFile {
+                    faculty: name.clone(),
+                    path: path.clone(),
+                });
+            }
+            if meta
+                .outcome_schema
+                .as_ref()
+                .is_some_and(|schema| !schema.is_object())
+            {
+                errors.push(FacultyError::BadOutcomeSchema {
+                    faculty: name.clone(),
+                });
+            }
+
+            for (hook, command) in hooks {
+                match std::fs::metadata(command) {
+                    Err(_) => errors.push(FacultyError::MissingCommand {
+                        faculty: name.clone(),
+                        hook: hook.to_string(),
+                        command: command.clone(),
+                    }),
+                    Ok(m) if !is_executable(&m) => errors.push(FacultyError::NotExecutable {
+                        faculty: name.clone(),
+                        hook: hook.to_string(),
+                        command: command.clone(),
+                    }),
+                    Ok(_) => {}
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Re-read the directory this registry was loaded from.
+    pub fn reload(&self) -> Result<Self> {
+        let dir = self
+            .dir
+            .as_deref()
+            .ok_or_else(|| Error::Config("faculty registry was not loaded from a dir".into()))?;
+        Self::load_from_dir(dir)
     }
 
     /// Look up a faculty by name.
     pub fn get(&self, name: &str) -> Option<&FacultyMeta> {
         self.faculties.get(name)
     }
+
+    /// All loaded faculties, sorted by name.
+    pub fn faculties(&self) -> Vec<&FacultyMeta> {
+        let mut all: Vec<&FacultyMeta> = self.faculties.values().collect();
+        all.sort_by(|a, b| a.name.cmp(&b.name));
+        all
+    }
+
+    /// Number of loaded faculties.
+    pub fn len(&self) -> usize {
+        self.faculties.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.faculties.is_empty()
+    }
+}
+
+/// Whether a hook command can be run: a file with an execute bit set.
+#[cfg(unix)]
+fn is_executable(meta: &std::fs::Metadata) -> bool {
+    meta.is_file() && meta.permissions().mode() & 0o111 != 0
+}
+
+/// Without Unix permission bits, any existing file counts as executable.
+#[cfg(not(unix))]
+fn is_executable(meta: &std::fs::Metadata) -> bool {
+    meta.is_file()
 }
@@ -8,6 +8,9 @@
 //! (pluggable cognitive specializations), LLM abstraction (rig-core), and
 //! observability (OpenTelemetry). All on Postgres.
 
+#[cfg(feature = "admin")]
+pub mod admin;
+pub mod clock;
 pub mod config;
 pub mod db;
 pub mod engine;
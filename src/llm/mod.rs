@@ -1,9 +1,17 @@
 //! LLM provider setup via rig-core.
 //!
-//! Provides a helper function to create an Anthropic [`Client`] from a
-//! [`SecretString`]-wrapped API key. The returned client can create
+//! Provides helper functions to create an Anthropic [`Client`] from a
+//! [`SecretString`]-wrapped API key, optionally pointed at a proxy or
+//! gateway and with a request timeout. The returned client can create
 //! `CompletionModel` instances via rig-core's [`CompletionClient`] trait.
 //!
+//! Anthropic has no embeddings API, so [`openai_client`] and
+//! [`openai_compatible_client`] cover the embedding side: their clients
+//! implement [`EmbeddingsClient`], producing the vectors that
+//! `store_memory` and `search_memory_by_vector` take.
+//!
+//! Wrap calls in [`with_retry`] to ride out rate limiting and overload.
+//!
 //! # Example
 //! ```no_run
 //! use animus_rs::llm::anthropic_client;
@@ -15,11 +23,28 @@
 //! let model = client.completion_model("claude-sonnet-4-20250514");
 //! ```
 //!
+//! ```no_run
+//! use animus_rs::llm::openai_client;
+//! use secrecy::SecretString;
+//! use rig::client::EmbeddingsClient;
+//!
+//! let key = SecretString::from("sk-...");
+//! let client = openai_client(&key).expect("failed to create OpenAI client");
+//! let embedder = client.embedding_model("text-embedding-3-small");
+//! ```
+//!
 //! [`Client`]: rig::providers::anthropic::Client
 //! [`SecretString`]: secrecy::SecretString
 //! [`CompletionClient`]: rig::client::CompletionClient
+//! [`EmbeddingsClient`]: rig::client::EmbeddingsClient
+
+pub mod retry;
+
+pub use retry::{RetryPolicy, Retryable, TokenUsage, with_retry};
 
+use rig::http_client::ReqwestClient;
 use secrecy::{ExposeSecret, SecretString};
+use std::time::Duration;
 
 /// Create an Anthropic client from a secret API key.
 ///
@@ -33,5 +58,69 @@ use secrecy::{ExposeSecret, SecretString};
 pub fn anthropic_client(
     api_key: &SecretString,
 ) -> Result<rig::providers::anthropic::Client, rig::http_client::Error> {
-    rig::providers::anthropic::Client::new(api_key.expose_secret())
+    anthropic_client_with_opts(api_key, None, None)
+}
+
+/// Create an Anthropic client with an optional base URL and request timeout.
+///
+/// `base_url` replaces `https://api.anthropic.com` — useful for proxies,
+/// gateways, and test servers. `timeout` bounds each HTTP request end to
+/// end; without it requests may wait indefinitely.
+///
+/// # Errors
+/// Returns an error if the underlying HTTP client cannot be constructed.
+pub fn anthropic_client_with_opts(
+    api_key: &SecretString,
+    base_url: Option<&str>,
+    timeout: Option<Duration>,
+) -> Result<rig::providers::anthropic::Client, rig::http_client::Error> {
+    let mut http = ReqwestClient::builder();
+    if let Some(timeout) = timeout {
+        http = http.timeout(timeout);
+    }
+    let http = http
+        .build()
+        .map_err(|e| rig::http_client::Error::Instance(Box::new(e)))?;
+
+    let mut builder = rig::providers::anthropic::Client::<ReqwestClient>::builder()
+        .api_key(api_key.expose_secret())
+        .http_client(http);
+    if let Some(url) = base_url {
+        builder = builder.base_url(url);
+    }
+    builder.build()
+}
+
+/// Create an OpenAI client from a secret API key.
+///
+/// Supports both completions (via the Responses API) and embeddings via
+/// [`EmbeddingsClient::embedding_model`].
+///
+/// [`EmbeddingsClient::embedding_model`]: rig::client::EmbeddingsClient::embedding_model
+///
+/// # Errors
+/// Returns an error if the underlying HTTP client cannot be constructed.
+pub fn openai_client(
+    api_key: &SecretString,
+) -> Result<rig::providers::openai::Client, rig::http_client::Error> {
+    rig::providers::openai::Client::new(api_key.expose_secret())
+}
+
+/// Create a client for an OpenAI-compatible server (Ollama, vLLM, LiteLLM, ...).
+///
+/// `base_url` includes the API prefix, e.g. `http://localhost:11434/v1`.
+/// Embeddings work the same as with [`openai_client`]. Most compatible
+/// servers implement Chat Completions rather than the Responses API; call
+/// `.completions_api()` on the client for completion models.
+///
+/// # Errors
+/// Returns an error if the underlying HTTP client cannot be constructed.
+pub fn openai_compatible_client(
+    api_key: &SecretString,
+    base_url: &str,
+) -> Result<rig::providers::openai::Client, rig::http_client::Error> {
+    rig::providers::openai::Client::<ReqwestClient>::builder()
+        .api_key(api_key.expose_secret())
+        .base_url(base_url)
+        .build()
 }
@@ -0,0 +1,223 @@
+//! Retry with exponential backoff for LLM calls.
+//!
+//! Providers shed load with 429 (rate limited) and 529/503 (overloaded)
+//! responses that clear within seconds. [`with_retry`] re-runs a call on
+//! those and on transport failures, backing off exponentially with jitter,
+//! and gives up immediately on anything a retry cannot fix (bad requests,
+//! auth, malformed responses).
+
+use crate::telemetry::metrics;
+use opentelemetry::KeyValue;
+use rig::completion::{CompletionError, CompletionResponse, PromptError, Usage};
+use rig::embeddings::{Embedding, EmbeddingError};
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+/// How many times to try an LLM call and how long to wait between tries.
+/// `provider` and `model` label the token and duration metrics.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total attempts, including the first.
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled for each one after.
+    pub initial_backoff: Duration,
+    /// Upper bound on any single delay.
+    pub max_backoff: Duration,
+    pub provider: String,
+    pub model: String,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            provider: "unknown".to_string(),
+            model: "unknown".to_string(),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Default retry settings with metric labels for `provider`/`model`.
+    pub fn for_model(provider: impl Into<String>, model: impl Into<String>) -> Self {
+        Self {
+            provider: provider.into(),
+            model: model.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Delay before retry number `retry` (1-based): exponential, capped,
+    /// with "equal jitter" — somewhere between half and all of the step —
+    /// so callers that failed together don't retry together.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let step = self
+            .initial_backoff
+            .saturating_mul(1u32 << (retry.saturating_sub(1)).min(16))
+            .min(self.max_backoff);
+        step.mul_f64(0.5 + fastrand::f64() * 0.5)
+    }
+}
+
+/// Whether an error is transient and worth retrying.
+pub trait Retryable {
+    fn is_retryable(&self) -> bool;
+}
+
+impl Retryable for rig::http_client::Error {
+    fn is_retryable(&self) -> bool {
+        use rig::http_client::Error;
+        match self {
+            Error::InvalidStatusCode(status) | Error::InvalidStatusCodeWithMessage(status, _) => {
+                status.as_u16() == 429 || status.is_server_error()
+            }
+            // Connection resets, timeouts, and other transport failures
+            Error::Instance(_) | Error::StreamEnded => true,
+            _ => false,
+        }
+    }
+}
+
+impl Retryable for CompletionError {
+    fn is_retryable(&self) -> bool {
+        match self {
+            CompletionError::HttpError(e) => e.is_retryable(),
+            CompletionError::ProviderError(message) => transient_provider_error(message),
+            _ => false,
+        }
+    }
+}
+
+impl Retryable for PromptError {
+    fn is_retryable(&self) -> bool {
+        match self {
+            PromptError::CompletionError(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+}
+
+impl Retryable for EmbeddingError {
+    fn is_retryable(&self) -> bool {
+        match self {
+            EmbeddingError::HttpError(e) => e.is_retryable(),
+            EmbeddingError::ProviderError(message) => transient_provider_error(message),
+            _ => false,
+        }
+    }
+}
+
+/// rig reports non-2xx responses as the provider's error body, without the
+/// status code, so rate limiting and overload are recognized by the error
+/// types Anthropic and OpenAI put in that body.
+fn transient_provider_error(message: &str) -> bool {
+    const MARKERS: &[&str] = &[
+        "rate_limit",
+        "rate limit",
+        "overloaded",
+        "api_error",
+        "server_error",
+        "timeout",
+    ];
+    let message = message.to_lowercase();
+    MARKERS.iter().any(|m| message.contains(m))
+}
+
+/// Token usage reported by an LLM response, recorded to the
+/// `animus.llm.tokens` counter on success.
+pub trait TokenUsage {
+    fn token_usage(&self) -> Option<Usage> {
+        None
+    }
+}
+
+impl<R> TokenUsage for CompletionResponse<R> {
+    fn token_usage(&self) -> Option<Usage> {
+        Some(self.usage)
+    }
+}
+
+impl TokenUsage for String {}
+impl TokenUsage for Embedding {}
+impl TokenUsage for Vec<Embedding> {}
+
+/// Run `call` until it succeeds, fails with a non-retryable error, or
+/// `policy.max_attempts` is reached; the last error is returned.
+///
+/// Every attempt records `animus.operation.duration_ms` (operation
+/// `llm.call`) and an event on the current span; a successful response's
+/// token usage is added to `animus.llm.tokens`.
+pub async fn with_retry<F, Fut, T, E>(policy: &RetryPolicy, mut call: F) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    T: TokenUsage,
+    E: Retryable + std::fmt::Display,
+{
+    let max_attempts = policy.max_attempts.max(1);
+    let mut attempt = 1;
+    loop {
+        let start = Instant::now();
+        let result = call().await;
+        let elapsed_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+        let outcome = match &result {
+            Ok(_) => "ok",
+            Err(e) if e.is_retryable() && attempt < max_attempts => "retry",
+            Err(_) => "error",
+        };
+        metrics::operation_duration_ms().record(
+            elapsed_ms,
+            &[
+                KeyValue::new("operation", "llm.call"),
+                KeyValue::new("provider", policy.provider.clone()),
+                KeyValue::new("model", policy.model.clone()),
+                KeyValue::new("result", outcome),
+            ],
+        );
+
+        match result {
+            Ok(response) => {
+                tracing::debug!(attempt, elapsed_ms, "llm call succeeded");
+                if let Some(usage) = response.token_usage() {
+                    record_tokens(policy, usage);
+                }
+                return Ok(response);
+            }
+            Err(e) if outcome == "retry" => {
+                let delay = policy.backoff(attempt);
+                tracing::warn!(
+                    attempt,
+                    elapsed_ms,
+                    delay_ms = delay.as_millis() as u64,
+                    "llm call failed, retrying: {e}"
+                );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            Err(e) => {
+                tracing::warn!(attempt, elapsed_ms, "llm call failed: {e}");
+                return Err(e);
+            }
+        }
+    }
+}
+
+fn record_tokens(policy: &RetryPolicy, usage: Usage) {
+    let counter = metrics::llm_tokens();
+    for (direction, tokens) in [
+        ("input", usage.input_tokens),
+        ("output", usage.output_tokens),
+    ] {
+        counter.add(
+            tokens,
+            &[
+                KeyValue::new("model", policy.model.clone()),
+                KeyValue::new("provider", policy.provider.clone()),
+                KeyValue::new("direction", direction),
+            ],
+        );
+    }
+}
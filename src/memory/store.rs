@@ -1,7 +1,7 @@
 //! Embedding storage, vector search, and hybrid BM25+vector search.
 
 use crate::db::Db;
-use crate::error::Result;
+use crate::error::{Error, Result};
 use crate::model::memory::*;
 use crate::telemetry::metrics;
 use opentelemetry::KeyValue;
@@ -25,6 +25,48 @@ impl Db {
         Ok(row.0)
     }
 
+    /// Delete a memory. Returns whether it existed.
+    pub async fn delete_memory(&self, id: i64) -> Result<bool> {
+        let rows_affected = sqlx::query("DELETE FROM memories WHERE id = $1")
+            .bind(id)
+            .execute(self.pool())
+            .await?
+            .rows_affected();
+        metrics::memory_operations().add(1, &[KeyValue::new("operation", "delete")]);
+        Ok(rows_affected > 0)
+    }
+
+    /// Update the given fields of a memory, leaving the rest unchanged, and
+    /// bump `updated_at`. The full-text index follows `content`; keeping the
+    /// embedding in step with new content is up to the caller.
+    pub async fn update_memory(
+        &self,
+        id: i64,
+        content: Option<&str>,
+        metadata: Option<&serde_json::Value>,
+        embedding: Option<&[f32]>,
+    ) -> Result<MemoryEntry> {
+        let row: Option<MemoryEntryRow> = sqlx::query_as(
+            "UPDATE memories SET
+                content = COALESCE($2, content),
+                metadata = COALESCE($3, metadata),
+                embedding = COALESCE($4::vector, embedding),
+                updated_at = now()
+             WHERE id = $1
+             RETURNING id, content, memory_type, source, metadata, created_at, updated_at",
+        )
+        .bind(id)
+        .bind(content)
+        .bind(metadata)
+        .bind(embedding.map(format_vector))
+        .fetch_optional(self.pool())
+        .await?;
+
+        metrics::memory_operations().add(1, &[KeyValue::new("operation", "update")]);
+        row.map(MemoryEntry::from)
+            .ok_or_else(|| Error::not_found(format!("memory {id}")))
+    }
+
     /// Search memories by vector similarity (cosine distance).
     pub async fn search_memory_by_vector(
         &self,
@@ -54,23 +96,71 @@ impl Db {
         Ok(rows.into_iter().map(MemoryEntry::from).collect())
     }
 
-    /// Hybrid search combining BM25 full-text score and vector similarity.
+    /// Vector search with a rerank stage: fetch `overfetch` candidates by
+    /// distance, let `rerank` order them, and keep the first `limit`.
+    ///
+    /// `rerank` returns indices into the candidate slice, best first.
+    /// Out-of-range and repeated indices are ignored, and candidates it
+    /// leaves out are dropped. Use [`identity_rerank`] to keep distance order.
+    pub async fn search_memory_reranked(
+        &self,
+        embedding: &[f32],
+        limit: i64,
+        overfetch: i64,
+        rerank: impl Fn(&[MemoryEntry]) -> Vec<usize>,
+        filters: &MemoryFilters,
+    ) -> Result<Vec<MemoryEntry>> {
+        let candidates = self
+            .search_memory_by_vector(embedding, overfetch.max(limit), filters)
+            .await?;
+
+        let order = rerank(&candidates);
+        let mut slots: Vec<Option<MemoryEntry>> = candidates.into_iter().map(Some).collect();
+        let mut results = Vec::new();
+        for i in order {
+            if results.len() as i64 >= limit {
+                break;
+            }
+            // take() leaves None behind, so repeats are skipped
+            if let Some(entry) = slots.get_mut(i).and_then(Option::take) {
+                results.push(entry);
+            }
+        }
+
+        metrics::memory_operations().add(1, &[KeyValue::new("operation", "reranked_search")]);
+        Ok(results)
+    }
+
+    /// Hybrid search combining BM25 full-text score and vector similarity,
+    /// blended by `weights`. Results carry both sub-scores for debugging.
     pub async fn hybrid_search(
         &self,
         text: &str,
         embedding: &[f32],
         limit: i64,
+        weights: HybridWeights,
         filters: &MemoryFilters,
     ) -> Result<Vec<MemoryEntry>> {
+        for (name, w) in [("vector", weights.vector), ("text", weights.text)] {
+            if !w.is_finite() || w < 0.0 {
+                return Err(Error::Config(format!(
+                    "hybrid {name} weight must be finite and non-negative, got {w}"
+                )));
+            }
+        }
+
         let rows: Vec<MemoryEntryRow> = sqlx::query_as(
-            "SELECT id, content, memory_type, source, metadata, created_at, updated_at
-             FROM memories
-             WHERE ($5::text IS NULL OR memory_type = $5)
-             AND ($6::text IS NULL OR source = $6)
-             AND ($7::timestamptz IS NULL OR created_at >= $7)
+            "SELECT * FROM (
+                SELECT id, content, memory_type, source, metadata, created_at, updated_at,
+                    (embedding <=> $1::vector)::float8 AS vector_distance,
+                    ts_rank(search_text, plainto_tsquery('english', $2))::float8 AS text_rank
+                FROM memories
+                WHERE ($5::text IS NULL OR memory_type = $5)
+                AND ($6::text IS NULL OR source = $6)
+                AND ($7::timestamptz IS NULL OR created_at >= $7)
+             ) scored
              ORDER BY
-                (1.0 / (1e-6 + (embedding <=> $1::vector))) * 0.7
-                + ts_rank(search_text, plainto_tsquery('english', $2)) * 0.3
+                (1.0 / (1e-6 + vector_distance)) * $8 + text_rank * $9
              DESC
              LIMIT $3",
         )
@@ -81,6 +171,8 @@ impl Db {
         .bind(filters.memory_type.as_deref())
         .bind(filters.source.as_deref())
         .bind(filters.since)
+        .bind(weights.vector)
+        .bind(weights.text)
         .fetch_all(self.pool())
         .await?;
 
@@ -89,6 +181,11 @@ impl Db {
     }
 }
 
+/// Reranker for [`Db::search_memory_reranked`] that keeps distance order.
+pub fn identity_rerank(candidates: &[MemoryEntry]) -> Vec<usize> {
+    (0..candidates.len()).collect()
+}
+
 /// Internal row type for sqlx::FromRow.
 #[derive(sqlx::FromRow)]
 struct MemoryEntryRow {
@@ -99,6 +196,10 @@ struct MemoryEntryRow {
     metadata: serde_json::Value,
     created_at: chrono::DateTime<chrono::Utc>,
     updated_at: chrono::DateTime<chrono::Utc>,
+    #[sqlx(default)]
+    vector_distance: Option<f64>,
+    #[sqlx(default)]
+    text_rank: Option<f64>,
 }
 
 impl From<MemoryEntryRow> for MemoryEntry {
@@ -111,12 +212,14 @@ impl From<MemoryEntryRow> for MemoryEntry {
             metadata: row.metadata,
             created_at: row.created_at,
             updated_at: row.updated_at,
+            vector_distance: row.vector_distance,
+            text_rank: row.text_rank,
         }
     }
 }
 
 /// Format a f32 slice as a pgvector string literal: `"[0.1,0.2,0.3]"`
-fn format_vector(v: &[f32]) -> String {
+pub(crate) fn format_vector(v: &[f32]) -> String {
     let inner: Vec<String> = v.iter().map(|x| x.to_string()).collect();
     format!("[{}]", inner.join(","))
 }
@@ -13,6 +13,12 @@ pub struct MemoryEntry {
     pub metadata: serde_json::Value,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Cosine distance to the query embedding. Set by `hybrid_search` only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vector_distance: Option<f64>,
+    /// Full-text `ts_rank` against the query text. Set by `hybrid_search` only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text_rank: Option<f64>,
 }
 
 /// Parameters for creating a new memory entry.
@@ -32,3 +38,19 @@ pub struct MemoryFilters {
     pub source: Option<String>,
     pub since: Option<DateTime<Utc>>,
 }
+
+/// Blend of vector similarity and full-text rank in hybrid search.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HybridWeights {
+    pub vector: f64,
+    pub text: f64,
+}
+
+impl Default for HybridWeights {
+    fn default() -> Self {
+        Self {
+            vector: 0.7,
+            text: 0.3,
+        }
+    }
+}
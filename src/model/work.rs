@@ -4,7 +4,9 @@
 //! provenance (where it came from), priority, and lifecycle state.
 
 use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use uuid::Uuid;
 
 // ---------------------------------------------------------------------------
@@ -17,6 +19,10 @@ pub struct WorkItem {
     /// Unique identifier.
     pub id: WorkId,
 
+    /// The pgmq queue this item is sent to.
+    #[serde(default = "default_queue")]
+    pub queue: String,
+
     /// Which faculty handles this work (e.g., "engineer", "social").
     pub faculty: String,
 
@@ -27,14 +33,19 @@ pub struct WorkItem {
     /// are candidates for dedup. None means no structural dedup.
     pub dedup_key: Option<String>,
 
+    /// Replaces `faculty` in the structural dedup match, so items of
+    /// different faculties with the same (dedup_scope, dedup_key) merge.
+    #[serde(default)]
+    pub dedup_scope: Option<String>,
+
     /// Where this work came from.
     pub provenance: Provenance,
 
     /// Arbitrary parameters for the worker. The engine doesn't interpret these.
     pub params: serde_json::Value,
 
-    /// Priority. Higher = more urgent. Engine provides base priority per
-    /// work type; provenance and age can boost it.
+    /// Priority. Higher = more urgent. Unless the submitter set one, this
+    /// is the faculty's base priority (see [`WorkTypeDefaults`]).
     pub priority: i32,
 
     /// Current lifecycle state.
@@ -54,10 +65,81 @@ pub struct WorkItem {
 
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// When the item reached a terminal state. Accepts `completed_at` from
+    /// older serialized items.
+    #[serde(alias = "completed_at")]
     pub resolved_at: Option<DateTime<Utc>>,
 
     /// Result of execution, populated on completion or failure.
     pub outcome: Option<Outcome>,
+
+    /// W3C trace context captured at submit time, so execution can join
+    /// the submitter's distributed trace.
+    pub trace_context: Option<HashMap<String, String>>,
+
+    /// Time after which the work is no longer useful. None = no deadline.
+    #[serde(default)]
+    pub deadline: Option<DateTime<Utc>>,
+
+    /// Time after which queued or failed work is dead-lettered rather than
+    /// run. None = never expires.
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+
+    /// Free-form labels for filtering (e.g. "team:infra"), sorted.
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+impl WorkItem {
+    /// Time from submission to resolution. None until the item is terminal.
+    pub fn lifecycle_duration(&self) -> Option<chrono::Duration> {
+        self.resolved_at.map(|resolved| resolved - self.created_at)
+    }
+
+    /// Time from the item's last change to `now`, which for a waiting or
+    /// running item is how long it has been in its current state.
+    pub fn time_in_current_state(&self, now: DateTime<Utc>) -> chrono::Duration {
+        now - self.updated_at
+    }
+
+    /// Has the item sat in Claimed or Running longer than `policy` allows,
+    /// as of `now`? Always false in other states.
+    pub fn is_stale(&self, policy: &StalenessPolicy, now: DateTime<Utc>) -> bool {
+        let limit = match self.state {
+            State::Claimed => policy.claimed_after,
+            State::Running => policy.running_after,
+            _ => return false,
+        };
+        (now - self.updated_at)
+            .to_std()
+            .is_ok_and(|idle| idle > limit)
+    }
+
+    /// Deserialize `params` into a typed struct.
+    pub fn params_as<T: DeserializeOwned>(&self) -> crate::error::Result<T> {
+        T::deserialize(&self.params)
+            .map_err(|e| crate::error::Error::InvalidState(format!("params: {e}")))
+    }
+}
+
+/// JSON type name, for error messages.
+fn json_kind(value: &serde_json::Value) -> &'static str {
+    match value {
+        serde_json::Value::Null => "null",
+        serde_json::Value::Bool(_) => "a boolean",
+        serde_json::Value::Number(_) => "a number",
+        serde_json::Value::String(_) => "a string",
+        serde_json::Value::Array(_) => "an array",
+        serde_json::Value::Object(_) => "an object",
+    }
+}
+
+/// Queue used when a submission doesn't name one.
+pub const DEFAULT_QUEUE: &str = "work";
+
+fn default_queue() -> String {
+    DEFAULT_QUEUE.to_string()
 }
 
 /// Newtype for work item IDs.
@@ -95,6 +177,8 @@ pub enum State {
     Created,
     /// Ready for execution, waiting for a worker.
     Queued,
+    /// Held back from execution until resumed (e.g. for maintenance).
+    Paused,
     /// Worker assigned, execution starting.
     Claimed,
     /// Worker actively processing.
@@ -110,6 +194,19 @@ pub enum State {
 }
 
 impl State {
+    /// Every state, in lifecycle order.
+    pub const ALL: [State; 9] = [
+        State::Created,
+        State::Queued,
+        State::Paused,
+        State::Claimed,
+        State::Running,
+        State::Completed,
+        State::Failed,
+        State::Dead,
+        State::Merged,
+    ];
+
     /// Can transition from self to `to`?
     pub fn can_transition_to(self, to: State) -> bool {
         use State::*;
@@ -119,12 +216,15 @@ impl State {
                 | (Created, Merged)
                 | (Queued, Claimed)
                 | (Queued, Dead)        // cancelled or circuit-broken
+                | (Queued, Paused)
+                | (Paused, Queued)
                 | (Claimed, Running)
                 | (Claimed, Queued)     // worker failed to start, re-queue
                 | (Running, Completed)
                 | (Running, Failed)
                 | (Failed, Queued)      // retry
-                | (Failed, Dead) // exhausted retries
+                | (Failed, Dead)        // exhausted retries
+                | (Dead, Queued) // operator replay
         )
     }
 
@@ -141,6 +241,7 @@ impl std::str::FromStr for State {
         match s {
             "created" => Ok(State::Created),
             "queued" => Ok(State::Queued),
+            "paused" => Ok(State::Paused),
             "claimed" => Ok(State::Claimed),
             "running" => Ok(State::Running),
             "completed" => Ok(State::Completed),
@@ -157,6 +258,7 @@ impl std::fmt::Display for State {
         let s = match self {
             State::Created => "created",
             State::Queued => "queued",
+            State::Paused => "paused",
             State::Claimed => "claimed",
             State::Running => "running",
             State::Completed => "completed",
@@ -182,10 +284,162 @@ pub struct Provenance {
     pub trigger: Option<String>,
 }
 
+/// Provenance of a submission that was merged into a canonical item.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProvenanceEntry {
+    /// The merged (duplicate) work item.
+    pub id: WorkId,
+    pub provenance: Provenance,
+    /// When the duplicate was submitted.
+    pub created_at: DateTime<Utc>,
+}
+
+/// How often, and from where, duplicates of a canonical item arrived.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DedupStats {
+    /// Submissions merged into the canonical item.
+    pub merge_count: u64,
+    /// First and last merged submission. None when nothing was merged.
+    pub first_seen: Option<DateTime<Utc>>,
+    pub last_seen: Option<DateTime<Utc>>,
+    /// Distinct provenance sources of the merged submissions, sorted.
+    pub sources: Vec<String>,
+}
+
+// ---------------------------------------------------------------------------
+// Events
+// ---------------------------------------------------------------------------
+
+/// What happened to a work item, as recorded in the event log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum EventKind {
+    /// A submission was accepted (it may be merged straight away).
+    Submitted {
+        faculty: String,
+        source: String,
+    },
+    /// The submission was recognized as a duplicate of `canonical`.
+    Merged {
+        canonical: WorkId,
+    },
+    StateChanged {
+        from: State,
+        to: State,
+    },
+    Reprioritized {
+        from: i32,
+        to: i32,
+    },
+    /// The item was held back from execution.
+    WorkPaused,
+    /// A paused item was released back to its queue.
+    WorkResumed,
+    /// The item outlived its TTL and was dead-lettered.
+    Expired,
+    /// The item was deleted by a retention purge of `count` items.
+    WorkPurged {
+        count: u64,
+    },
+}
+
+impl EventKind {
+    /// The `kind` tag, as stored alongside the event for filtering.
+    pub fn name(&self) -> &'static str {
+        match self {
+            EventKind::Submitted { .. } => "submitted",
+            EventKind::Merged { .. } => "merged",
+            EventKind::StateChanged { .. } => "state_changed",
+            EventKind::Reprioritized { .. } => "reprioritized",
+            EventKind::WorkPaused => "work_paused",
+            EventKind::WorkResumed => "work_resumed",
+            EventKind::Expired => "expired",
+            EventKind::WorkPurged { .. } => "work_purged",
+        }
+    }
+}
+
+/// An entry in the work event log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Event {
+    /// Increasing in commit order across all work items.
+    pub seq: i64,
+    pub work_id: WorkId,
+    #[serde(flatten)]
+    pub kind: EventKind,
+    pub created_at: DateTime<Utc>,
+}
+
+// ---------------------------------------------------------------------------
+// Logs
+// ---------------------------------------------------------------------------
+
+/// Severity of a work log line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl std::str::FromStr for LogLevel {
+    type Err = crate::error::Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "debug" => Ok(LogLevel::Debug),
+            "info" => Ok(LogLevel::Info),
+            "warn" => Ok(LogLevel::Warn),
+            "error" => Ok(LogLevel::Error),
+            other => Err(crate::error::Error::InvalidState(format!(
+                "unknown log level: {other}"
+            ))),
+        }
+    }
+}
+
+impl std::fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            LogLevel::Debug => "debug",
+            LogLevel::Info => "info",
+            LogLevel::Warn => "warn",
+            LogLevel::Error => "error",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// A line in a work item's durable log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogEntry {
+    /// Increasing across all work items.
+    pub id: i64,
+    pub work_id: WorkId,
+    pub level: LogLevel,
+    pub message: String,
+    pub created_at: DateTime<Utc>,
+}
+
 // ---------------------------------------------------------------------------
 // Outcome
 // ---------------------------------------------------------------------------
 
+/// One execution attempt of a work item.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttemptRecord {
+    /// 1-based, counting across operator replays.
+    pub attempt: u32,
+    pub started_at: DateTime<Utc>,
+    /// None while the attempt is still running.
+    pub ended_at: Option<DateTime<Utc>>,
+    /// State the attempt ended in (completed or failed).
+    pub outcome: Option<State>,
+    pub error: Option<String>,
+}
+
 /// Result of work execution, stored with the work item on completion.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Outcome {
@@ -198,39 +452,234 @@ pub struct Outcome {
     pub duration_ms: u64,
 }
 
+impl Outcome {
+    /// Deserialize `data` into a typed struct. None when there is no data.
+    pub fn data_as<T: DeserializeOwned>(&self) -> crate::error::Result<Option<T>> {
+        self.data
+            .as_ref()
+            .map(T::deserialize)
+            .transpose()
+            .map_err(|e| crate::error::Error::InvalidState(format!("outcome data: {e}")))
+    }
+}
+
+/// How long an item may sit in Claimed or Running, measured from its last
+/// update, before it counts as stuck (e.g. its worker died).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StalenessPolicy {
+    pub claimed_after: std::time::Duration,
+    pub running_after: std::time::Duration,
+}
+
+/// Attempts allowed when a work item doesn't set `max_attempts`.
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+
+/// Per-faculty submission defaults, applied when a [`NewWorkItem`] leaves
+/// the field unset (see
+/// [`Db::with_work_type_defaults`](crate::db::Db::with_work_type_defaults)).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkTypeDefaults {
+    pub base_priority: i32,
+    /// None = [`DEFAULT_MAX_ATTEMPTS`].
+    pub max_attempts: Option<u32>,
+}
+
+/// Named priorities, so producers share one scale. Raw values from
+/// [`NewWorkItem::priority`] still work alongside them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PriorityLevel {
+    Low,
+    Normal,
+    High,
+    Critical,
+}
+
+impl PriorityLevel {
+    /// The raw priority this level stands for.
+    pub fn value(self) -> i32 {
+        match self {
+            PriorityLevel::Low => 0,
+            PriorityLevel::Normal => 10,
+            PriorityLevel::High => 20,
+            PriorityLevel::Critical => 30,
+        }
+    }
+}
+
+impl std::str::FromStr for PriorityLevel {
+    type Err = crate::error::Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "low" => Ok(PriorityLevel::Low),
+            "normal" => Ok(PriorityLevel::Normal),
+            "high" => Ok(PriorityLevel::High),
+            "critical" => Ok(PriorityLevel::Critical),
+            other => Err(crate::error::Error::InvalidState(format!(
+                "unknown priority level: {other}"
+            ))),
+        }
+    }
+}
+
+impl std::fmt::Display for PriorityLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            PriorityLevel::Low => "low",
+            PriorityLevel::Normal => "normal",
+            PriorityLevel::High => "high",
+            PriorityLevel::Critical => "critical",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// Accepted range of priorities (see
+/// [`Db::with_priority_scheme`](crate::db::Db::with_priority_scheme)).
+/// The default spans the named levels, [`PriorityLevel::Low`] to
+/// [`PriorityLevel::Critical`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PriorityScheme {
+    pub min: i32,
+    pub max: i32,
+}
+
+impl PriorityScheme {
+    /// Reject `priority` if it falls outside `min..=max`.
+    pub fn check(&self, priority: i32) -> crate::error::Result<()> {
+        if (self.min..=self.max).contains(&priority) {
+            Ok(())
+        } else {
+            Err(crate::error::Error::InvalidState(format!(
+                "priority {priority} is outside {}..={}",
+                self.min, self.max
+            )))
+        }
+    }
+}
+
+impl Default for PriorityScheme {
+    fn default() -> Self {
+        Self {
+            min: PriorityLevel::Low.value(),
+            max: PriorityLevel::Critical.value(),
+        }
+    }
+}
+
+/// How a failure should be handled, decided when it happens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureClass {
+    /// Requeue immediately, while attempts remain.
+    Retryable,
+    /// Go straight to dead.
+    NonRetryable,
+    /// Requeue after the delay, while attempts remain.
+    RetryAfter(std::time::Duration),
+}
+
 // ---------------------------------------------------------------------------
 // Builder
 // ---------------------------------------------------------------------------
 
 /// Builder for creating new work items. The engine's public API for submitting work.
 pub struct NewWorkItem {
+    pub(crate) queue: String,
     pub(crate) faculty: String,
     pub(crate) skill: Option<String>,
     pub(crate) dedup_key: Option<String>,
+    pub(crate) dedup_scope: Option<String>,
     pub(crate) provenance: Provenance,
     pub(crate) params: serde_json::Value,
-    pub(crate) priority: i32,
+    pub(crate) priority: Option<i32>,
     pub(crate) parent_id: Option<WorkId>,
     pub(crate) max_attempts: Option<u32>,
+    pub(crate) trace_context: Option<HashMap<String, String>>,
+    pub(crate) deadline: Option<DateTime<Utc>>,
+    pub(crate) ttl: Option<std::time::Duration>,
+    pub(crate) tags: Vec<String>,
+    pub(crate) embedding: Option<Vec<f32>>,
+    pub(crate) idempotency_key: Option<String>,
 }
 
+/// Longest accepted dedup key, in bytes.
+pub const MAX_DEDUP_KEY_LEN: usize = 256;
+
+/// Deepest accepted parent chain, counting the new item, unless the
+/// [`Db`](crate::db::Db) is configured otherwise.
+pub const DEFAULT_MAX_PARENT_DEPTH: u32 = 100;
+
 impl NewWorkItem {
+    /// Check the fields every submission needs: a non-blank faculty and
+    /// source, a dedup key no longer than [`MAX_DEDUP_KEY_LEN`], and a
+    /// dedup key wherever there is a dedup scope.
+    pub fn validate(&self) -> crate::error::Result<()> {
+        let invalid = |msg: String| Err(crate::error::Error::InvalidState(msg));
+        if self.faculty.trim().is_empty() {
+            return invalid("work item faculty is empty".to_string());
+        }
+        if self.provenance.source.trim().is_empty() {
+            return invalid("work item source is empty".to_string());
+        }
+        if let Some(ref key) = self.dedup_key
+            && key.len() > MAX_DEDUP_KEY_LEN
+        {
+            return invalid(format!(
+                "dedup key is {} bytes, max is {MAX_DEDUP_KEY_LEN}",
+                key.len()
+            ));
+        }
+        if self.dedup_scope.is_some() && self.dedup_key.is_none() {
+            return invalid("dedup scope is set without a dedup key".to_string());
+        }
+        Ok(())
+    }
+
+    /// [`validate`](Self::validate), plus params must be a JSON object
+    /// (or absent).
+    pub fn validate_strict(&self) -> crate::error::Result<()> {
+        self.validate()?;
+        if !(self.params.is_object() || self.params.is_null()) {
+            return Err(crate::error::Error::InvalidState(format!(
+                "work item params must be a JSON object, got {}",
+                json_kind(&self.params)
+            )));
+        }
+        Ok(())
+    }
+
     pub fn new(faculty: impl Into<String>, source: impl Into<String>) -> Self {
         Self {
+            queue: default_queue(),
             faculty: faculty.into(),
             skill: None,
             dedup_key: None,
+            dedup_scope: None,
             provenance: Provenance {
                 source: source.into(),
                 trigger: None,
             },
             params: serde_json::Value::Null,
-            priority: 0,
+            priority: None,
             parent_id: None,
             max_attempts: None,
+            trace_context: None,
+            deadline: None,
+            ttl: None,
+            tags: Vec::new(),
+            embedding: None,
+            idempotency_key: None,
         }
     }
 
+    /// Send to `queue` instead of the default `work` queue. The queue must
+    /// exist (see [`Db::create_queue`](crate::db::Db::create_queue)).
+    pub fn queue(mut self, queue: impl Into<String>) -> Self {
+        self.queue = queue.into();
+        self
+    }
+
     pub fn skill(mut self, skill: impl Into<String>) -> Self {
         self.skill = Some(skill.into());
         self
@@ -241,6 +690,13 @@ impl NewWorkItem {
         self
     }
 
+    /// Dedup against items of any faculty sharing this scope and the dedup
+    /// key, instead of only this faculty's. Needs a dedup key.
+    pub fn dedup_scope(mut self, scope: impl Into<String>) -> Self {
+        self.dedup_scope = Some(scope.into());
+        self
+    }
+
     pub fn trigger(mut self, trigger: impl Into<String>) -> Self {
         self.provenance.trigger = Some(trigger.into());
         self
@@ -251,11 +707,24 @@ impl NewWorkItem {
         self
     }
 
+    /// Set `params` from any serializable value, in place of hand-built JSON.
+    pub fn typed_params<T: Serialize>(mut self, params: &T) -> crate::error::Result<Self> {
+        self.params = serde_json::to_value(params)
+            .map_err(|e| crate::error::Error::Other(format!("serialize params: {e}")))?;
+        Ok(self)
+    }
+
+    /// Overrides the faculty's base priority. Unset, that default is 0.
     pub fn priority(mut self, priority: i32) -> Self {
-        self.priority = priority;
+        self.priority = Some(priority);
         self
     }
 
+    /// [`priority`](Self::priority) from a named level.
+    pub fn priority_level(self, level: PriorityLevel) -> Self {
+        self.priority(level.value())
+    }
+
     pub fn parent(mut self, parent_id: WorkId) -> Self {
         self.parent_id = Some(parent_id);
         self
@@ -265,4 +734,51 @@ impl NewWorkItem {
         self.max_attempts = Some(n);
         self
     }
+
+    /// Carry a propagated W3C trace context (e.g. from
+    /// [`current_trace_context`](crate::telemetry::work::current_trace_context))
+    /// so the executing control plane parents its work span on it.
+    pub fn trace_context(mut self, propagated: HashMap<String, String>) -> Self {
+        self.trace_context = Some(propagated);
+        self
+    }
+
+    /// Only useful if done before `at`. Overdue work is still executed,
+    /// but is reported by [`Db::list_overdue`](crate::db::Db::list_overdue).
+    pub fn deadline(mut self, at: DateTime<Utc>) -> Self {
+        self.deadline = Some(at);
+        self
+    }
+
+    /// Expire the work `ttl` after submission: unlike a deadline, expired
+    /// work is dead-lettered with "ttl expired" instead of being run (see
+    /// [`Db::expire_overdue`](crate::db::Db::expire_overdue)).
+    pub fn ttl(mut self, ttl: std::time::Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Embedding of the work's intent, for semantic dedup (see
+    /// [`Db::with_semantic_dedup_threshold`](crate::db::Db::with_semantic_dedup_threshold)).
+    pub fn embedding(mut self, embedding: Vec<f32>) -> Self {
+        self.embedding = Some(embedding);
+        self
+    }
+
+    /// Guard against double submission: a resubmit with the same key within
+    /// the idempotency window (see
+    /// [`Db::with_idempotency_window`](crate::db::Db::with_idempotency_window))
+    /// returns the original item, whatever its state, instead of creating
+    /// or merging. Unlike the dedup key, this is about the request, not the
+    /// work.
+    pub fn idempotency_key(mut self, key: impl Into<String>) -> Self {
+        self.idempotency_key = Some(key.into());
+        self
+    }
+
+    /// Attach a label. Tags don't affect dedup or lifecycle.
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
 }
@@ -3,7 +3,11 @@
 //! Uses the OTel Meter API with the globally-registered `MeterProvider`.
 //! All instruments are created lazily from the `"animus-rs"` meter.
 
-use opentelemetry::metrics::{Counter, Histogram, Meter};
+use crate::model::work::State;
+use opentelemetry::KeyValue;
+use opentelemetry::metrics::{Counter, Histogram, Meter, ObservableGauge};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
 
 /// Returns the shared meter for animus-rs instruments.
 fn meter() -> Meter {
@@ -37,7 +41,8 @@ pub fn queue_operations() -> Counter<u64> {
         .build()
 }
 
-/// Counter: memory store operations (store, search, hybrid_search).
+/// Counter: memory store operations (store, update, delete, and the
+/// vector, hybrid, and reranked searches).
 /// Labels: `operation`.
 pub fn memory_operations() -> Counter<u64> {
     meter()
@@ -47,7 +52,9 @@ pub fn memory_operations() -> Counter<u64> {
 }
 
 /// Histogram: operation duration in milliseconds.
-/// Labels: `operation`.
+/// Labels: `operation`; focus operations (`focus.run`, `focus.phase`) add
+/// `faculty`, `result`, and for phases `phase`; LLM calls (`llm.call`)
+/// add `provider`, `model`, and `result` ("ok" | "retry" | "error").
 pub fn operation_duration_ms() -> Histogram<f64> {
     meter()
         .f64_histogram("animus.operation.duration_ms")
@@ -73,3 +80,83 @@ pub fn work_unroutable() -> Counter<u64> {
         .with_description("Work items with no matching faculty")
         .build()
 }
+
+/// Counter: work items picked up after their deadline had passed.
+/// Labels: `faculty`.
+pub fn work_overdue() -> Counter<u64> {
+    meter()
+        .u64_counter("animus.work.overdue")
+        .with_description("Work items executed past their deadline")
+        .build()
+}
+
+/// Counter: work items dead-lettered because their faculty's circuit was open.
+/// Labels: `faculty`.
+pub fn work_circuit_rejected() -> Counter<u64> {
+    meter()
+        .u64_counter("animus.work.circuit_rejected")
+        .with_description("Work items dead-lettered by an open circuit")
+        .build()
+}
+
+/// Counter: queue messages moved to a poison queue as unparseable.
+/// Labels: `queue`.
+pub fn work_poisoned() -> Counter<u64> {
+    meter()
+        .u64_counter("animus.work.poisoned")
+        .with_description("Queue messages moved to a poison queue")
+        .build()
+}
+
+/// Counter: foci spawned by the control plane.
+/// Labels: `faculty`.
+pub fn focus_spawned() -> Counter<u64> {
+    meter()
+        .u64_counter("animus.focus.spawned")
+        .with_description("Foci spawned")
+        .build()
+}
+
+/// Counter: foci whose pipeline finished and whose work was retired.
+/// Labels: `faculty`, `result` ("completed" | "failed").
+pub fn focus_completed() -> Counter<u64> {
+    meter()
+        .u64_counter("animus.focus.completed")
+        .with_description("Foci finished and retired")
+        .build()
+}
+
+/// Counter: focus directories that could not be removed after a focus
+/// finished, and so are left on disk.
+/// Labels: `kind` (the IO error kind, or "other").
+pub fn focus_cleanup_failures() -> Counter<u64> {
+    meter()
+        .u64_counter("animus.focus.cleanup_failures")
+        .with_description("Focus directories left behind by failed cleanup")
+        .build()
+}
+
+/// Counter: terminal work items deleted by retention purges.
+pub fn work_purged() -> Counter<u64> {
+    meter()
+        .u64_counter("animus.work.purged")
+        .with_description("Terminal work items deleted by retention purges")
+        .build()
+}
+
+/// Gauge: work items currently in each state, read from `counts` at each
+/// collection. The owner keeps `counts` fresh (e.g. from
+/// `Db::count_by_state`); the gauge only reports it.
+/// Labels: `state`.
+pub fn work_in_state(counts: Arc<RwLock<HashMap<State, u64>>>) -> ObservableGauge<u64> {
+    meter()
+        .u64_observable_gauge("animus.work.in_state")
+        .with_description("Work items currently in each state")
+        .with_callback(move |observer| {
+            let counts = counts.read().expect("work state counts lock poisoned");
+            for (state, n) in counts.iter() {
+                observer.observe(*n, &[KeyValue::new("state", state.to_string())]);
+            }
+        })
+        .build()
+}
@@ -9,14 +9,164 @@ pub mod metrics;
 pub mod work;
 
 use crate::error::{Error, Result};
+use std::time::Duration;
+
+/// Transport for OTLP export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OtlpProtocol {
+    /// gRPC, conventionally on port 4317.
+    #[default]
+    Grpc,
+    /// HTTP with protobuf bodies, conventionally on port 4318. Signals are
+    /// posted to `/v1/traces`, `/v1/metrics`, and `/v1/logs` under the
+    /// endpoint.
+    HttpBinary,
+}
+
+impl std::str::FromStr for OtlpProtocol {
+    type Err = Error;
+
+    /// Parses the standard `OTEL_EXPORTER_OTLP_PROTOCOL` values.
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "grpc" => Ok(Self::Grpc),
+            "http/protobuf" => Ok(Self::HttpBinary),
+            other => Err(Error::Config(format!("unsupported OTLP protocol: {other}"))),
+        }
+    }
+}
+
+/// Which traces are recorded and exported.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SamplingConfig {
+    /// Every trace.
+    #[default]
+    AlwaysOn,
+    /// This fraction of traces, chosen by trace id.
+    TraceIdRatio(f64),
+    /// Follow the parent span's decision; new roots are sampled at this
+    /// ratio. Keeps traces propagated from submitters whole.
+    ParentBased(f64),
+}
+
+impl SamplingConfig {
+    fn sampler(self) -> opentelemetry_sdk::trace::Sampler {
+        use opentelemetry_sdk::trace::Sampler;
+        match self {
+            Self::AlwaysOn => Sampler::AlwaysOn,
+            Self::TraceIdRatio(ratio) => Sampler::TraceIdRatioBased(ratio),
+            Self::ParentBased(ratio) => {
+                Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(ratio)))
+            }
+        }
+    }
+}
 
 /// Configuration for telemetry initialization.
+#[derive(Debug, Clone)]
 pub struct TelemetryConfig {
     /// Optional OTLP endpoint (e.g. "http://localhost:4317").
     /// When `None`, telemetry uses a simple fmt layer for local dev.
     pub endpoint: Option<String>,
+    /// How to reach `endpoint`.
+    pub protocol: OtlpProtocol,
     /// The service name reported in telemetry signals.
     pub service_name: String,
+    /// Timeout for each export request. None = SDK default.
+    pub export_timeout: Option<Duration>,
+    /// How long spans and logs are batched before export, and the metric
+    /// export interval. None = SDK defaults.
+    pub scheduled_delay: Option<Duration>,
+    /// Spans (and, separately, logs) buffered for export before new ones
+    /// are dropped. None = SDK default.
+    pub max_queue_size: Option<usize>,
+    /// Trace sampling. Defaults to every trace.
+    pub sampling: SamplingConfig,
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: None,
+            protocol: OtlpProtocol::Grpc,
+            service_name: "animus".to_string(),
+            export_timeout: None,
+            scheduled_delay: None,
+            max_queue_size: None,
+            sampling: SamplingConfig::AlwaysOn,
+        }
+    }
+}
+
+impl TelemetryConfig {
+    /// Read telemetry settings from the environment: `OTEL_ENDPOINT`,
+    /// `OTEL_PROTOCOL` (`grpc` or `http/protobuf`), `OTEL_EXPORT_TIMEOUT_MS`,
+    /// `OTEL_SCHEDULED_DELAY_MS`, `OTEL_MAX_QUEUE_SIZE`, and the standard
+    /// `OTEL_TRACES_SAMPLER` (`always_on`, `traceidratio`,
+    /// `parentbased_traceidratio`) with its ratio in
+    /// `OTEL_TRACES_SAMPLER_ARG` (default 1.0). Unset variables keep their
+    /// defaults.
+    pub fn from_env(service_name: impl Into<String>) -> Result<Self> {
+        let millis = |name: &str| env_parse::<u64>(name).map(|ms| ms.map(Duration::from_millis));
+        Ok(Self {
+            endpoint: std::env::var("OTEL_ENDPOINT").ok(),
+            protocol: env_parse("OTEL_PROTOCOL")?.unwrap_or_default(),
+            service_name: service_name.into(),
+            export_timeout: millis("OTEL_EXPORT_TIMEOUT_MS")?,
+            scheduled_delay: millis("OTEL_SCHEDULED_DELAY_MS")?,
+            max_queue_size: env_parse("OTEL_MAX_QUEUE_SIZE")?,
+            sampling: sampling_from_env()?,
+        })
+    }
+}
+
+fn sampling_from_env() -> Result<SamplingConfig> {
+    let ratio = env_parse::<f64>("OTEL_TRACES_SAMPLER_ARG")?.unwrap_or(1.0);
+    match std::env::var("OTEL_TRACES_SAMPLER").as_deref() {
+        Err(_) | Ok("always_on") => Ok(SamplingConfig::AlwaysOn),
+        Ok("traceidratio") => Ok(SamplingConfig::TraceIdRatio(ratio)),
+        Ok("parentbased_traceidratio") => Ok(SamplingConfig::ParentBased(ratio)),
+        Ok(other) => Err(Error::Config(format!(
+            "unsupported OTEL_TRACES_SAMPLER: {other}"
+        ))),
+    }
+}
+
+/// Parse an optional environment variable, failing on a malformed value.
+fn env_parse<T: std::str::FromStr>(name: &str) -> Result<Option<T>> {
+    match std::env::var(name) {
+        Ok(value) => value
+            .parse()
+            .map(Some)
+            .map_err(|_| Error::Config(format!("invalid {name}: {value}"))),
+        Err(_) => Ok(None),
+    }
+}
+
+/// Build an OTLP exporter from `config`; HTTP posts to `endpoint/path`.
+macro_rules! otlp_exporter {
+    ($builder:ty, $config:expr, $endpoint:expr, $path:literal, $what:literal) => {
+        match $config.protocol {
+            OtlpProtocol::Grpc => {
+                let mut builder = <$builder>::builder().with_tonic().with_endpoint($endpoint);
+                if let Some(timeout) = $config.export_timeout {
+                    builder = builder.with_timeout(timeout);
+                }
+                builder.build()
+            }
+            OtlpProtocol::HttpBinary => {
+                let mut builder = <$builder>::builder()
+                    .with_http()
+                    .with_protocol(opentelemetry_otlp::Protocol::HttpBinary)
+                    .with_endpoint(format!("{}{}", $endpoint.trim_end_matches('/'), $path));
+                if let Some(timeout) = $config.export_timeout {
+                    builder = builder.with_timeout(timeout);
+                }
+                builder.build()
+            }
+        }
+        .map_err(|e| Error::Other(format!("failed to create OTLP {} exporter: {e}", $what)))?
+    };
 }
 
 /// Guard that shuts down OTel providers on drop.
@@ -86,14 +236,27 @@ pub fn init_telemetry(config: TelemetryConfig) -> Result<TelemetryGuard> {
             .build();
 
         // --- Traces ---
-        let span_exporter = opentelemetry_otlp::SpanExporter::builder()
-            .with_tonic()
-            .with_endpoint(&endpoint)
-            .build()
-            .map_err(|e| Error::Other(format!("failed to create OTLP span exporter: {e}")))?;
+        let span_exporter = otlp_exporter!(
+            opentelemetry_otlp::SpanExporter,
+            config,
+            &endpoint,
+            "/v1/traces",
+            "span"
+        );
 
+        let mut batch = opentelemetry_sdk::trace::BatchConfigBuilder::default();
+        if let Some(delay) = config.scheduled_delay {
+            batch = batch.with_scheduled_delay(delay);
+        }
+        if let Some(size) = config.max_queue_size {
+            batch = batch.with_max_queue_size(size);
+        }
+        let span_processor = opentelemetry_sdk::trace::BatchSpanProcessor::builder(span_exporter)
+            .with_batch_config(batch.build())
+            .build();
         let tracer_provider = opentelemetry_sdk::trace::SdkTracerProvider::builder()
-            .with_batch_exporter(span_exporter)
+            .with_span_processor(span_processor)
+            .with_sampler(config.sampling.sampler())
             .with_resource(resource.clone())
             .build();
 
@@ -101,28 +264,46 @@ pub fn init_telemetry(config: TelemetryConfig) -> Result<TelemetryGuard> {
         let otel_trace_layer = tracing_opentelemetry::layer().with_tracer(tracer);
 
         // --- Metrics ---
-        let metric_exporter = opentelemetry_otlp::MetricExporter::builder()
-            .with_tonic()
-            .with_endpoint(&endpoint)
-            .build()
-            .map_err(|e| Error::Other(format!("failed to create OTLP metric exporter: {e}")))?;
+        let metric_exporter = otlp_exporter!(
+            opentelemetry_otlp::MetricExporter,
+            config,
+            &endpoint,
+            "/v1/metrics",
+            "metric"
+        );
 
+        let mut reader = opentelemetry_sdk::metrics::PeriodicReader::builder(metric_exporter);
+        if let Some(interval) = config.scheduled_delay {
+            reader = reader.with_interval(interval);
+        }
         let meter_provider = opentelemetry_sdk::metrics::SdkMeterProvider::builder()
-            .with_periodic_exporter(metric_exporter)
+            .with_reader(reader.build())
             .with_resource(resource.clone())
             .build();
 
         opentelemetry::global::set_meter_provider(meter_provider.clone());
 
         // --- Logs ---
-        let log_exporter = opentelemetry_otlp::LogExporter::builder()
-            .with_tonic()
-            .with_endpoint(&endpoint)
-            .build()
-            .map_err(|e| Error::Other(format!("failed to create OTLP log exporter: {e}")))?;
+        let log_exporter = otlp_exporter!(
+            opentelemetry_otlp::LogExporter,
+            config,
+            &endpoint,
+            "/v1/logs",
+            "log"
+        );
 
+        let mut batch = opentelemetry_sdk::logs::BatchConfigBuilder::default();
+        if let Some(delay) = config.scheduled_delay {
+            batch = batch.with_scheduled_delay(delay);
+        }
+        if let Some(size) = config.max_queue_size {
+            batch = batch.with_max_queue_size(size);
+        }
+        let log_processor = opentelemetry_sdk::logs::BatchLogProcessor::builder(log_exporter)
+            .with_batch_config(batch.build())
+            .build();
         let logger_provider = opentelemetry_sdk::logs::SdkLoggerProvider::builder()
-            .with_batch_exporter(log_exporter)
+            .with_log_processor(log_processor)
             .with_resource(resource)
             .build();
 
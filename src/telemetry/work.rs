@@ -3,7 +3,11 @@
 //! Provides span creation and state-transition recording for work items
 //! flowing through the engine.
 
+use opentelemetry::propagation::TextMapPropagator as _;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use std::collections::HashMap;
 use tracing::Span;
+use tracing_opentelemetry::OpenTelemetrySpanExt as _;
 use uuid::Uuid;
 
 /// Start a span for work item execution.
@@ -19,6 +23,62 @@ pub fn start_work_span(faculty: &str, work_id: &Uuid) -> Span {
     )
 }
 
+/// Start a work span whose parent is a propagated W3C trace context.
+///
+/// Used when the work item was submitted from another service: the
+/// context captured at submit time (see [`current_trace_context`]) becomes
+/// the span's parent, stitching the trace across the submit/execute
+/// boundary. An empty or invalid context leaves the span as a new root.
+pub fn start_work_span_with_parent(
+    faculty: &str,
+    work_id: &Uuid,
+    parent: &HashMap<String, String>,
+) -> Span {
+    let span = start_work_span(faculty, work_id);
+    let cx = TraceContextPropagator::new().extract(parent);
+    // Fails only when no OTel layer is installed — nothing to stitch then.
+    let _ = span.set_parent(cx);
+    span
+}
+
+/// Capture the current span's W3C trace context (`traceparent`,
+/// `tracestate`) for attaching to submitted work.
+pub fn current_trace_context() -> HashMap<String, String> {
+    let mut carrier = HashMap::new();
+    TraceContextPropagator::new().inject_context(&Span::current().context(), &mut carrier);
+    carrier
+}
+
+/// The current span's trace context as `TRACEPARENT` (and `TRACESTATE`)
+/// environment variables, so a child process can continue the trace.
+/// Empty when there is no active trace.
+pub fn trace_env() -> Vec<(String, String)> {
+    current_trace_context()
+        .into_iter()
+        .map(|(key, value)| (key.to_ascii_uppercase(), value))
+        .collect()
+}
+
+/// Record attributes reported by a hook on `span`. Strings, numbers, and
+/// booleans are recorded; other values are skipped.
+pub fn record_span_attributes(
+    span: &Span,
+    attributes: &serde_json::Map<String, serde_json::Value>,
+) {
+    for (key, value) in attributes {
+        let value: opentelemetry::Value = match value {
+            serde_json::Value::String(s) => s.clone().into(),
+            serde_json::Value::Bool(b) => (*b).into(),
+            serde_json::Value::Number(n) => match n.as_i64() {
+                Some(i) => i.into(),
+                None => n.as_f64().unwrap_or_default().into(),
+            },
+            _ => continue,
+        };
+        span.set_attribute(key.clone(), value);
+    }
+}
+
 /// Record a state transition event on the current span.
 ///
 /// Emits a tracing `info` event scoped to the given span.
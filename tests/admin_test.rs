@@ -0,0 +1,132 @@
+//! Admin HTTP API against a live database. Run with `--features admin`.
+#![cfg(feature = "admin")]
+
+use animus_rs::db::Db;
+use animus_rs::engine::{ControlConfig, ControlPlane};
+use animus_rs::faculty::FacultyRegistry;
+use std::sync::Arc;
+
+#[tokio::test]
+#[ignore] // Requires running Postgres with pgmq
+async fn admin_api_submits_and_reads_work() {
+    dotenvy::dotenv().ok();
+    let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let db = Db::connect(&url).await.expect("db connect");
+    db.migrate().await.expect("migrate");
+    db.create_queue("work").await.expect("create queue");
+    let db = Arc::new(db);
+    let control = ControlPlane::new(
+        Arc::clone(&db),
+        Arc::new(FacultyRegistry::empty()),
+        ControlConfig::default(),
+        1,
+    );
+
+    let port = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let addr: std::net::SocketAddr = format!("127.0.0.1:{port}").parse().unwrap();
+    let server = tokio::spawn(animus_rs::admin::serve(addr, Arc::clone(&db), control));
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    let base = format!("http://{addr}");
+    let client = reqwest::Client::new();
+
+    let faculty = format!("admin-{}", uuid::Uuid::new_v4());
+    let created = client
+        .post(format!("{base}/work"))
+        .json(&serde_json::json!({ "faculty": faculty, "source": "test", "params": { "n": 1 } }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(created.status(), 201);
+    let created: serde_json::Value = created.json().await.unwrap();
+    let id = created["id"].as_str().unwrap().to_string();
+
+    let item: serde_json::Value = client
+        .get(format!("{base}/work/{id}"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(item["params"]["n"], 1);
+
+    let listed: Vec<serde_json::Value> = client
+        .get(format!("{base}/work?faculty={faculty}&state=queued"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(listed.len(), 1);
+
+    let work_id = animus_rs::model::work::WorkId(uuid::Uuid::parse_str(&id).unwrap());
+    db.append_log(
+        work_id,
+        animus_rs::model::work::LogLevel::Info,
+        "from the test",
+    )
+    .await
+    .unwrap();
+    let logs: Vec<serde_json::Value> = client
+        .get(format!("{base}/work/{id}/logs"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0]["message"], "from the test");
+
+    // Following the event log from just before the submit finds it
+    let submitted_seq = db.get_events(work_id).await.unwrap()[0].seq;
+    let since = submitted_seq - 1;
+    let after: Vec<serde_json::Value> = client
+        .get(format!("{base}/events?since={since}&limit=1"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(after.len(), 1);
+    assert_eq!(after[0]["seq"], submitted_seq);
+    assert_eq!(after[0]["work_id"], id.as_str());
+    assert_eq!(after[0]["kind"], "submitted");
+    let bad_since = client
+        .get(format!("{base}/events?since=soon"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(bad_since.status(), 400);
+
+    let missing = client
+        .get(format!("{base}/work/{}", uuid::Uuid::new_v4()))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(missing.status(), 404);
+    let missing_logs = client
+        .get(format!("{base}/work/{}/logs", uuid::Uuid::new_v4()))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(missing_logs.status(), 404);
+    let bad = client
+        .get(format!("{base}/work?state=bogus"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(bad.status(), 400);
+
+    // Empty registry: not ready
+    let health = client.get(format!("{base}/health")).send().await.unwrap();
+    assert_eq!(health.status(), 503);
+
+    server.abort();
+}
@@ -0,0 +1,53 @@
+use animus_rs::engine::{CircuitBreaker, CircuitConfig, CircuitState};
+use std::time::Duration;
+
+fn breaker(cooldown: Duration) -> CircuitBreaker {
+    CircuitBreaker::new(CircuitConfig {
+        threshold: 3,
+        window: Duration::from_secs(60),
+        cooldown,
+    })
+}
+
+#[test]
+fn circuit_opens_at_threshold_per_faculty() {
+    let circuit = breaker(Duration::from_secs(60));
+
+    assert!(!circuit.record_failure("engage"));
+    assert!(!circuit.record_failure("engage"));
+    assert_eq!(circuit.state("engage"), CircuitState::Closed);
+    assert!(circuit.record_failure("engage"));
+    assert!(matches!(circuit.state("engage"), CircuitState::Open { .. }));
+
+    // Other faculties are unaffected
+    assert_eq!(circuit.state("transform"), CircuitState::Closed);
+}
+
+#[test]
+fn circuit_closes_after_cooldown() {
+    let circuit = breaker(Duration::from_millis(50));
+    for _ in 0..3 {
+        circuit.record_failure("engage");
+    }
+    assert!(matches!(circuit.state("engage"), CircuitState::Open { .. }));
+
+    std::thread::sleep(Duration::from_millis(80));
+    assert_eq!(circuit.state("engage"), CircuitState::Closed);
+
+    // Failure history was cleared along with the open state
+    assert!(!circuit.record_failure("engage"));
+    assert_eq!(circuit.state("engage"), CircuitState::Closed);
+}
+
+#[test]
+fn failures_outside_the_window_do_not_count() {
+    let circuit = CircuitBreaker::new(CircuitConfig {
+        threshold: 2,
+        window: Duration::from_millis(30),
+        cooldown: Duration::from_secs(60),
+    });
+    circuit.record_failure("engage");
+    std::thread::sleep(Duration::from_millis(50));
+    assert!(!circuit.record_failure("engage"));
+    assert_eq!(circuit.state("engage"), CircuitState::Closed);
+}
@@ -0,0 +1,32 @@
+use animus_rs::clock::{Clock, MockClock, SystemClock};
+use chrono::{Duration, TimeZone, Utc};
+use std::sync::Arc;
+
+#[test]
+fn mock_clock_moves_only_when_told() {
+    let start = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+    let clock = MockClock::new(start);
+    assert_eq!(clock.now(), start);
+    assert_eq!(clock.now(), start);
+
+    clock.advance(Duration::minutes(90));
+    assert_eq!(clock.now(), start + Duration::minutes(90));
+
+    clock.set(start);
+    assert_eq!(clock.now(), start);
+}
+
+#[test]
+fn shared_mock_clock_is_a_clock() {
+    let clock = Arc::new(MockClock::default());
+    let shared: Box<dyn Clock> = Box::new(clock.clone());
+    clock.advance(Duration::days(1));
+    assert_eq!(shared.now(), clock.now());
+}
+
+#[test]
+fn system_clock_tracks_wall_time() {
+    let before = Utc::now();
+    let now = SystemClock.now();
+    assert!(now >= before && now <= Utc::now());
+}
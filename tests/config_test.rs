@@ -28,3 +28,60 @@ fn config_from_env_fails_without_required() {
     let result = Config::from_env();
     assert!(result.is_err());
 }
+
+#[test]
+fn telemetry_config_from_env_reads_export_settings() {
+    use animus_rs::telemetry::{OtlpProtocol, SamplingConfig, TelemetryConfig};
+    use std::time::Duration;
+
+    unsafe {
+        std::env::set_var("OTEL_PROTOCOL", "http/protobuf");
+        std::env::set_var("OTEL_EXPORT_TIMEOUT_MS", "2500");
+        std::env::set_var("OTEL_SCHEDULED_DELAY_MS", "200");
+        std::env::set_var("OTEL_MAX_QUEUE_SIZE", "512");
+    }
+    let config = TelemetryConfig::from_env("animus-test").unwrap();
+    assert_eq!(config.protocol, OtlpProtocol::HttpBinary);
+    assert_eq!(config.export_timeout, Some(Duration::from_millis(2500)));
+    assert_eq!(config.scheduled_delay, Some(Duration::from_millis(200)));
+    assert_eq!(config.max_queue_size, Some(512));
+
+    unsafe {
+        std::env::set_var("OTEL_MAX_QUEUE_SIZE", "lots");
+    }
+    let err = TelemetryConfig::from_env("animus-test").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "configuration error: invalid OTEL_MAX_QUEUE_SIZE: lots"
+    );
+
+    unsafe {
+        std::env::remove_var("OTEL_PROTOCOL");
+        std::env::remove_var("OTEL_EXPORT_TIMEOUT_MS");
+        std::env::remove_var("OTEL_SCHEDULED_DELAY_MS");
+        std::env::remove_var("OTEL_MAX_QUEUE_SIZE");
+    }
+    let config = TelemetryConfig::from_env("animus-test").unwrap();
+    assert_eq!(config.protocol, OtlpProtocol::Grpc);
+    assert_eq!(config.scheduled_delay, None);
+    assert_eq!(config.sampling, SamplingConfig::AlwaysOn);
+
+    // Sampling shares the test: from_env reads every variable, so tests
+    // setting them can't run in parallel
+    unsafe {
+        std::env::set_var("OTEL_TRACES_SAMPLER", "parentbased_traceidratio");
+        std::env::set_var("OTEL_TRACES_SAMPLER_ARG", "0.25");
+    }
+    let config = TelemetryConfig::from_env("animus-test").unwrap();
+    assert_eq!(config.sampling, SamplingConfig::ParentBased(0.25));
+
+    unsafe {
+        std::env::set_var("OTEL_TRACES_SAMPLER", "always_off");
+    }
+    assert!(TelemetryConfig::from_env("animus-test").is_err());
+
+    unsafe {
+        std::env::remove_var("OTEL_TRACES_SAMPLER");
+        std::env::remove_var("OTEL_TRACES_SAMPLER_ARG");
+    }
+}
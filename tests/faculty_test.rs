@@ -3,10 +3,11 @@
 //! Requires the docker stack: `docker compose up -d`
 
 use animus_rs::db::Db;
-use animus_rs::engine::{ControlConfig, ControlPlane};
-use animus_rs::faculty::FacultyRegistry;
-use animus_rs::model::work::{NewWorkItem, State};
+use animus_rs::engine::{ControlConfig, ControlPlane, QueueConfig};
+use animus_rs::faculty::{FacultyError, FacultyRegistry};
+use animus_rs::model::work::{NewWorkItem, State, WorkId};
 use animus_rs::telemetry::{TelemetryConfig, init_telemetry};
+use std::os::unix::fs::PermissionsExt;
 use std::path::Path;
 use std::sync::Arc;
 
@@ -34,6 +35,8 @@ async fn unroutable_work_stays_queued() {
         focus_base_dir: focus_base.clone(),
         visibility_timeout: 2, // short timeout so message reappears quickly
         poll_interval: std::time::Duration::from_millis(200),
+        drain_timeout: std::time::Duration::from_secs(2),
+        ..ControlConfig::default()
     };
 
     let control = ControlPlane::new(Arc::clone(&db), Arc::new(registry), config, 4);
@@ -59,7 +62,7 @@ async fn unroutable_work_stays_queued() {
 
     let work_id = match result {
         animus_rs::db::work::SubmitResult::Created(item) => item.id,
-        animus_rs::db::work::SubmitResult::Merged { .. } => panic!("unexpected merge"),
+        other => panic!("expected Created, got {other:?}"),
     };
 
     // Give the control plane time to see the work and decide
@@ -80,6 +83,66 @@ async fn unroutable_work_stays_queued() {
     let _ = tokio::fs::remove_dir_all(&focus_base).await;
 }
 
+/// Unroutable work backs off between reads and is dead-lettered once its
+/// reads run out.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[ignore] // requires docker compose up -d
+async fn unroutable_work_is_dead_lettered_after_max_reads() {
+    dotenvy::dotenv().ok();
+    let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let db = Db::connect(&url).await.expect("db connect");
+    db.migrate().await.expect("migrate");
+    let queue = format!(
+        "unroutable_{}",
+        &uuid::Uuid::new_v4().simple().to_string()[..8]
+    );
+    db.create_queue(&queue).await.expect("create queue");
+    let db = Arc::new(db);
+
+    let focus_base = std::env::temp_dir()
+        .join("animus-test")
+        .join(uuid::Uuid::new_v4().to_string());
+    let config = ControlConfig {
+        focus_base_dir: focus_base.clone(),
+        visibility_timeout: 1,
+        poll_interval: std::time::Duration::from_millis(200),
+        queues: vec![QueueConfig::new(&queue)],
+        unroutable_max_reads: 2,
+        unroutable_backoff_cap: std::time::Duration::from_secs(1),
+        ..ControlConfig::default()
+    };
+    let control = ControlPlane::new(
+        Arc::clone(&db),
+        Arc::new(FacultyRegistry::empty()),
+        config,
+        4,
+    );
+    let ctrl = control.clone();
+    let handle = tokio::spawn(async move {
+        ctrl.run().await.expect("control plane run");
+    });
+
+    let work_id = submit_created(&db, NewWorkItem::new("unknown_type", "test").queue(&queue)).await;
+
+    let deadline = tokio::time::Instant::now() + std::time::Duration::from_secs(10);
+    let item = loop {
+        let item = db.get_work_item(work_id).await.expect("get work item");
+        if item.state == State::Dead || tokio::time::Instant::now() > deadline {
+            break item;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    };
+    assert_eq!(item.state, State::Dead);
+    assert_eq!(
+        item.outcome.and_then(|o| o.error).as_deref(),
+        Some("no faculty after 2 attempts")
+    );
+
+    control.shutdown();
+    let _ = tokio::time::timeout(std::time::Duration::from_secs(2), handle).await;
+    let _ = tokio::fs::remove_dir_all(&focus_base).await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 #[ignore] // requires docker compose up -d
 async fn transform_faculty_end_to_end() {
@@ -90,6 +153,7 @@ async fn transform_faculty_end_to_end() {
     let _guard = init_telemetry(TelemetryConfig {
         endpoint: Some(otel_endpoint),
         service_name: "animus-test".to_string(),
+        ..TelemetryConfig::default()
     })
     .expect("init telemetry");
 
@@ -114,6 +178,8 @@ async fn transform_faculty_end_to_end() {
         focus_base_dir: focus_base.clone(),
         visibility_timeout: 30,
         poll_interval: std::time::Duration::from_millis(500),
+        drain_timeout: std::time::Duration::from_secs(5),
+        ..ControlConfig::default()
     };
 
     let control = ControlPlane::new(Arc::clone(&db), Arc::new(registry), config, 4);
@@ -140,7 +206,7 @@ async fn transform_faculty_end_to_end() {
 
     let work_id = match result {
         animus_rs::db::work::SubmitResult::Created(item) => item.id,
-        animus_rs::db::work::SubmitResult::Merged { .. } => panic!("unexpected merge"),
+        other => panic!("expected Created, got {other:?}"),
     };
 
     // Poll for completion (10s timeout)
@@ -194,3 +260,631 @@ async fn transform_faculty_end_to_end() {
     // Cleanup
     let _ = tokio::fs::remove_dir_all(&focus_base).await;
 }
+
+/// Write a minimal faculty TOML named `name` into `dir`.
+fn write_faculty(dir: &Path, name: &str) {
+    std::fs::write(
+        dir.join(format!("{name}.toml")),
+        format!(
+            "[faculty]\nname = \"{name}\"\n\n[faculty.engage]\ncommand = \"/bin/true\"\n\n\
+             [faculty.recover]\ncommand = \"/bin/true\"\nmax_attempts = 1\n"
+        ),
+    )
+    .unwrap();
+}
+
+#[test]
+fn registry_reload_picks_up_new_faculties() {
+    let dir = std::env::temp_dir()
+        .join("animus-faculty-test")
+        .join(uuid::Uuid::new_v4().to_string());
+    std::fs::create_dir_all(&dir).unwrap();
+    write_faculty(&dir, "first");
+
+    let registry = FacultyRegistry::load_from_dir(&dir).unwrap();
+    assert!(registry.get("first").is_some());
+    assert!(registry.get("second").is_none());
+
+    write_faculty(&dir, "second");
+    let reloaded = registry.reload().unwrap();
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert!(reloaded.get("first").is_some());
+    assert!(reloaded.get("second").is_some());
+    // The old snapshot is untouched.
+    assert!(registry.get("second").is_none());
+    assert!(FacultyRegistry::empty().reload().is_err());
+}
+
+#[test]
+fn control_config_filters_work_types() {
+    let all = ControlConfig::default();
+    assert!(all.accepts("engineer"));
+
+    let sharded = ControlConfig {
+        accept_types: Some(vec!["engineer".to_string(), "social".to_string()]),
+        reject_types: vec!["social".to_string()],
+        ..ControlConfig::default()
+    };
+    assert!(sharded.accepts("engineer"));
+    assert!(!sharded.accepts("social"));
+    assert!(!sharded.accepts("transform"));
+}
+
+#[test]
+fn outcome_schema_reports_every_violation() {
+    use animus_rs::faculty::schema;
+
+    let schema: serde_json::Value = toml::from_str::<toml::Value>(
+        r#"
+        type = "object"
+        required = ["summary", "files"]
+        additionalProperties = false
+        [properties.summary]
+        type = "string"
+        minLength = 1
+        [properties.files]
+        type = "array"
+        items = { type = "string" }
+        [properties.status]
+        enum = ["ok", "partial"]
+        "#,
+    )
+    .map(|v| serde_json::to_value(v).unwrap())
+    .unwrap();
+
+    let good = serde_json::json!({ "summary": "done", "files": ["a.rs"], "status": "ok" });
+    assert!(schema::validate(&schema, &good).is_ok());
+
+    let bad = serde_json::json!({ "summary": "", "files": ["a.rs", 3], "status": "?", "extra": 1 });
+    let errors = schema::validate(&schema, &bad).unwrap_err();
+    assert_eq!(
+        errors,
+        [
+            "/extra: unexpected field",
+            "/files/1: expected string, got number",
+            r#"/status: "?" is not one of ["ok","partial"]"#,
+            "/summary: shorter than 1 characters",
+        ]
+    );
+
+    let errors = schema::validate(&schema, &serde_json::json!([])).unwrap_err();
+    assert_eq!(errors, ["/: expected object, got array"]);
+}
+
+#[test]
+fn load_from_dir_reports_every_bad_file() {
+    let dir = std::env::temp_dir()
+        .join("animus-faculty-test")
+        .join(uuid::Uuid::new_v4().to_string());
+    std::fs::create_dir_all(&dir).unwrap();
+    write_faculty(&dir, "good");
+    std::fs::write(dir.join("broken.toml"), "[faculty\nname = ").unwrap();
+    std::fs::write(dir.join("incomplete.toml"), "[faculty]\nname = \"x\"\n").unwrap();
+    std::fs::copy(dir.join("good.toml"), dir.join("good-copy.toml")).unwrap();
+
+    let err = FacultyRegistry::load_from_dir(&dir)
+        .err()
+        .unwrap()
+        .to_string();
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert!(err.contains("3 faculty config error(s)"), "{err}");
+    assert!(err.contains("broken.toml"), "{err}");
+    assert!(err.contains("incomplete.toml"), "{err}");
+    assert!(err.contains("faculty good defined in both"), "{err}");
+}
+
+#[test]
+fn validate_reports_missing_and_non_executable_commands() {
+    let dir = std::env::temp_dir()
+        .join("animus-faculty-test")
+        .join(uuid::Uuid::new_v4().to_string());
+    std::fs::create_dir_all(&dir).unwrap();
+    let plain = dir.join("plain.sh");
+    std::fs::write(&plain, "#!/bin/sh\n").unwrap();
+    std::fs::write(
+        dir.join("bad.toml"),
+        format!(
+            "[faculty]\nname = \"bad\"\n\n[faculty.engage]\ncommand = \"{}\"\n\n\
+             [faculty.recover]\ncommand = \"{}\"\nmax_attempts = 1\n",
+            dir.join("missing.sh").display(),
+            plain.display()
+        ),
+    )
+    .unwrap();
+    write_faculty(&dir, "good");
+
+    let registry = FacultyRegistry::load_from_dir(&dir).unwrap();
+    let errors = registry.validate().unwrap_err();
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert_eq!(errors.len(), 2, "{errors:?}");
+    assert!(matches!(
+        &errors[0],
+        FacultyError::MissingCommand { faculty, hook, .. } if faculty == "bad" && hook == "engage"
+    ));
+    assert!(matches!(
+        &errors[1],
+        FacultyError::NotExecutable { faculty, hook, .. } if faculty == "bad" && hook == "recover"
+    ));
+    assert!(
+        FacultyRegistry::load_from_dir(Path::new("fixtures/faculties"))
+            .unwrap()
+            .validate()
+            .is_ok()
+    );
+}
+
+/// Write a concurrent faculty whose engage hook sleeps 1.5s and records
+/// when it ran as `{"start", "end"}`. Returns the faculty name.
+fn write_slow_faculty(dir: &Path) -> String {
+    std::fs::create_dir_all(dir).unwrap();
+    let engage = dir.join("slow.sh");
+    std::fs::write(
+        &engage,
+        "#!/bin/sh\nstart=$(date +%s.%N)\nsleep 1.5\n\
+         printf '{\"start\":%s,\"end\":%s}' \"$start\" \"$(date +%s.%N)\" > engage-out.json\n",
+    )
+    .unwrap();
+    std::fs::set_permissions(&engage, std::fs::Permissions::from_mode(0o755)).unwrap();
+    let faculty = format!("slow-{}", uuid::Uuid::new_v4());
+    std::fs::write(
+        dir.join("slow.toml"),
+        format!(
+            "[faculty]\nname = \"{faculty}\"\nconcurrent = true\n\n\
+             [faculty.engage]\ncommand = \"{}\"\n\n\
+             [faculty.recover]\ncommand = \"/bin/true\"\nmax_attempts = 1\n",
+            engage.display()
+        ),
+    )
+    .unwrap();
+    faculty
+}
+
+/// Submit `new` and return the created item's id.
+/// Reads from earlier retries or pauses don't count toward the unroutable
+/// limit: only reads that found no faculty do.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[ignore] // requires docker compose up -d
+async fn unroutable_limit_ignores_earlier_reads() {
+    dotenvy::dotenv().ok();
+    let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let db = Db::connect(&url).await.expect("db connect");
+    db.migrate().await.expect("migrate");
+    let queue = format!("x_{}", &uuid::Uuid::new_v4().simple().to_string()[..8]);
+    db.create_queue(&queue).await.expect("create queue");
+    let db = Arc::new(db);
+
+    let work_id = submit_created(&db, NewWorkItem::new("unknown_type", "test").queue(&queue)).await;
+
+    // Stand in for a history of retries: read the message several times
+    // without running it, leaving pgmq's read count past the limit.
+    for _ in 0..3 {
+        db.read_from_queue(&queue, 0)
+            .await
+            .expect("read")
+            .expect("message visible");
+    }
+
+    let focus_base = std::env::temp_dir()
+        .join("animus-test")
+        .join(uuid::Uuid::new_v4().to_string());
+    let config = ControlConfig {
+        focus_base_dir: focus_base.clone(),
+        visibility_timeout: 1,
+        poll_interval: std::time::Duration::from_millis(200),
+        queues: vec![QueueConfig::new(&queue)],
+        unroutable_max_reads: 2,
+        unroutable_backoff_cap: std::time::Duration::from_secs(60),
+        ..ControlConfig::default()
+    };
+    let control = ControlPlane::new(
+        Arc::clone(&db),
+        Arc::new(FacultyRegistry::empty()),
+        config,
+        4,
+    );
+    let ctrl = control.clone();
+    let handle = tokio::spawn(async move {
+        ctrl.run().await.expect("control plane run");
+    });
+
+    // One unroutable read backs the item off; it is not dead-lettered
+    // on the strength of the earlier reads.
+    tokio::time::sleep(std::time::Duration::from_millis(1500)).await;
+    let item = db.get_work_item(work_id).await.expect("get work item");
+    assert_eq!(item.state, State::Queued);
+
+    control.shutdown();
+    let _ = tokio::time::timeout(std::time::Duration::from_secs(2), handle).await;
+    let _ = tokio::fs::remove_dir_all(&focus_base).await;
+}
+
+async fn submit_created(db: &Db, new: NewWorkItem) -> WorkId {
+    match db.submit_work(new).await.expect("submit work") {
+        animus_rs::db::work::SubmitResult::Created(item) => item.id,
+        other => panic!("expected Created, got {other:?}"),
+    }
+}
+
+/// Wait for each item to complete and return its `(start, end)` run times.
+async fn wait_for_runs(db: &Db, ids: &[WorkId]) -> Vec<(f64, f64)> {
+    let deadline = tokio::time::Instant::now() + std::time::Duration::from_secs(10);
+    let mut runs = Vec::new();
+    for id in ids {
+        loop {
+            let item = db.get_work_item(*id).await.expect("get work item");
+            if item.state == State::Completed {
+                let data = item.outcome.and_then(|o| o.data).expect("outcome data");
+                runs.push((
+                    data["start"].as_f64().unwrap(),
+                    data["end"].as_f64().unwrap(),
+                ));
+                break;
+            }
+            assert!(
+                tokio::time::Instant::now() < deadline,
+                "item {id} stuck in {:?}",
+                item.state
+            );
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        }
+    }
+    runs
+}
+
+/// Whether two `(start, end)` runs overlap in time.
+fn overlap(a: (f64, f64), b: (f64, f64)) -> bool {
+    a.0 < b.1 && b.0 < a.1
+}
+
+/// With `max_concurrent = 2`, two slow items submitted together run at the
+/// same time rather than one after the other.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[ignore] // requires docker compose up -d
+async fn foci_run_concurrently_up_to_max() {
+    dotenvy::dotenv().ok();
+    let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let db = Db::connect(&url).await.expect("db connect");
+    db.migrate().await.expect("migrate");
+    db.create_queue("work").await.expect("create queue");
+    let db = Arc::new(db);
+
+    let dir = std::env::temp_dir()
+        .join("animus-test")
+        .join(uuid::Uuid::new_v4().to_string());
+    let faculty = write_slow_faculty(&dir);
+    let registry = FacultyRegistry::load_from_dir(&dir).expect("load faculties");
+
+    let config = ControlConfig {
+        focus_base_dir: dir.join("foci"),
+        visibility_timeout: 30,
+        poll_interval: std::time::Duration::from_millis(200),
+        drain_timeout: std::time::Duration::from_secs(5),
+        ..ControlConfig::default()
+    };
+    let control = ControlPlane::new(Arc::clone(&db), Arc::new(registry), config, 2);
+    let ctrl = control.clone();
+    let handle = tokio::spawn(async move {
+        ctrl.run().await.expect("control plane run");
+    });
+    tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+
+    let mut ids = Vec::new();
+    for _ in 0..2 {
+        ids.push(submit_created(&db, NewWorkItem::new(&faculty, "test")).await);
+    }
+
+    // Serial execution would take ~3s
+    let runs = wait_for_runs(&db, &ids).await;
+
+    control.shutdown();
+    let _ = tokio::time::timeout(std::time::Duration::from_secs(5), handle).await;
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert!(overlap(runs[0], runs[1]), "foci did not overlap: {runs:?}");
+}
+
+/// Work on separate queues is consumed from each, and a per-queue cap
+/// serializes its queue while the other runs alongside.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[ignore] // requires docker compose up -d
+async fn control_plane_serves_multiple_queues() {
+    dotenvy::dotenv().ok();
+    let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let db = Db::connect(&url).await.expect("db connect");
+    db.migrate().await.expect("migrate");
+    let suffix = &uuid::Uuid::new_v4().simple().to_string()[..8];
+    let (fast, batch) = (format!("fast_{suffix}"), format!("batch_{suffix}"));
+    db.create_queue(&fast).await.expect("create queue");
+    db.create_queue(&batch).await.expect("create queue");
+    let db = Arc::new(db);
+
+    let dir = std::env::temp_dir()
+        .join("animus-test")
+        .join(uuid::Uuid::new_v4().to_string());
+    let faculty = write_slow_faculty(&dir);
+    let registry = FacultyRegistry::load_from_dir(&dir).expect("load faculties");
+
+    let config = ControlConfig {
+        focus_base_dir: dir.join("foci"),
+        poll_interval: std::time::Duration::from_millis(200),
+        queues: vec![
+            QueueConfig::new(&fast),
+            QueueConfig::new(&batch).max_concurrent(1),
+        ],
+        ..ControlConfig::default()
+    };
+    let control = ControlPlane::new(Arc::clone(&db), Arc::new(registry), config, 4);
+    let ctrl = control.clone();
+    let handle = tokio::spawn(async move {
+        ctrl.run().await.expect("control plane run");
+    });
+    tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+
+    let batch_ids = [
+        submit_created(&db, NewWorkItem::new(&faculty, "test").queue(&batch)).await,
+        submit_created(&db, NewWorkItem::new(&faculty, "test").queue(&batch)).await,
+    ];
+    let fast_id = submit_created(&db, NewWorkItem::new(&faculty, "test").queue(&fast)).await;
+
+    let batch_runs = wait_for_runs(&db, &batch_ids).await;
+    let fast_run = wait_for_runs(&db, &[fast_id]).await[0];
+    assert_eq!(db.get_work_item(fast_id).await.unwrap().queue, fast);
+
+    control.shutdown();
+    let _ = tokio::time::timeout(std::time::Duration::from_secs(5), handle).await;
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert!(
+        !overlap(batch_runs[0], batch_runs[1]),
+        "batch queue exceeded its cap: {batch_runs:?}"
+    );
+    assert!(
+        batch_runs.iter().any(|r| overlap(*r, fast_run)),
+        "fast work waited behind the batch queue: {fast_run:?} vs {batch_runs:?}"
+    );
+}
+
+/// Leftover focus directories are swept once past the orphan age.
+#[tokio::test]
+#[ignore] // requires docker compose up -d
+async fn sweep_removes_orphaned_focus_dirs() {
+    dotenvy::dotenv().ok();
+    let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let db = Arc::new(Db::connect(&url).await.expect("db connect"));
+
+    let focus_base = std::env::temp_dir()
+        .join("animus-test")
+        .join(uuid::Uuid::new_v4().to_string());
+    let orphan = focus_base.join(uuid::Uuid::new_v4().to_string());
+    std::fs::create_dir_all(&orphan).unwrap();
+    std::fs::write(orphan.join("work.json"), "{}").unwrap();
+
+    let config = ControlConfig {
+        focus_base_dir: focus_base.clone(),
+        ..ControlConfig::default()
+    };
+    let control = ControlPlane::new(
+        Arc::clone(&db),
+        Arc::new(FacultyRegistry::empty()),
+        config.clone(),
+        1,
+    );
+    assert_eq!(control.sweep_orphaned_foci().await.unwrap(), 0);
+    assert!(orphan.exists(), "fresh directory swept too early");
+
+    let control = ControlPlane::new(
+        db,
+        Arc::new(FacultyRegistry::empty()),
+        ControlConfig {
+            orphan_age: std::time::Duration::ZERO,
+            ..config
+        },
+        1,
+    );
+    assert_eq!(control.sweep_orphaned_foci().await.unwrap(), 1);
+    assert!(!orphan.exists());
+    let _ = std::fs::remove_dir_all(&focus_base);
+}
+
+/// Over the focus quota, work stays queued instead of being spawned.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[ignore] // requires docker compose up -d
+async fn focus_quota_holds_work_in_queue() {
+    dotenvy::dotenv().ok();
+    let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let db = Db::connect(&url).await.expect("db connect");
+    db.migrate().await.expect("migrate");
+    let queue = format!("quota_{}", &uuid::Uuid::new_v4().simple().to_string()[..8]);
+    db.create_queue(&queue).await.expect("create queue");
+    let db = Arc::new(db);
+
+    let dir = std::env::temp_dir()
+        .join("animus-test")
+        .join(uuid::Uuid::new_v4().to_string());
+    let faculty = write_slow_faculty(&dir);
+    let registry = FacultyRegistry::load_from_dir(&dir).expect("load faculties");
+    std::fs::create_dir_all(dir.join("foci")).unwrap();
+    std::fs::write(dir.join("foci").join("ballast"), [0u8; 64]).unwrap();
+
+    let config = ControlConfig {
+        focus_base_dir: dir.join("foci"),
+        poll_interval: std::time::Duration::from_millis(200),
+        queues: vec![QueueConfig::new(&queue)],
+        focus_quota_bytes: Some(16),
+        ..ControlConfig::default()
+    };
+    let control = ControlPlane::new(Arc::clone(&db), Arc::new(registry), config, 4);
+    let ctrl = control.clone();
+    let handle = tokio::spawn(async move {
+        ctrl.run().await.expect("control plane run");
+    });
+    tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+
+    let id = submit_created(&db, NewWorkItem::new(&faculty, "test").queue(&queue)).await;
+    tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+    assert_eq!(db.get_work_item(id).await.unwrap().state, State::Queued);
+
+    control.shutdown();
+    let _ = tokio::time::timeout(std::time::Duration::from_secs(5), handle).await;
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// A focus outlasting the visibility timeout keeps its message hidden.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[ignore] // requires docker compose up -d
+async fn long_focus_renews_its_lease() {
+    dotenvy::dotenv().ok();
+    let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let db = Db::connect(&url).await.expect("db connect");
+    db.migrate().await.expect("migrate");
+    let queue = format!("lease_{}", &uuid::Uuid::new_v4().simple().to_string()[..8]);
+    db.create_queue(&queue).await.expect("create queue");
+    let db = Arc::new(db);
+
+    let dir = std::env::temp_dir()
+        .join("animus-test")
+        .join(uuid::Uuid::new_v4().to_string());
+    let faculty = write_slow_faculty(&dir);
+    let registry = FacultyRegistry::load_from_dir(&dir).expect("load faculties");
+
+    let config = ControlConfig {
+        focus_base_dir: dir.join("foci"),
+        visibility_timeout: 1,
+        poll_interval: std::time::Duration::from_millis(200),
+        queues: vec![QueueConfig::new(&queue)],
+        ..ControlConfig::default()
+    };
+    let control = ControlPlane::new(Arc::clone(&db), Arc::new(registry), config, 4);
+    let ctrl = control.clone();
+    let handle = tokio::spawn(async move {
+        ctrl.run().await.expect("control plane run");
+    });
+    tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+
+    let id = submit_created(&db, NewWorkItem::new(&faculty, "test").queue(&queue)).await;
+    tokio::time::sleep(std::time::Duration::from_millis(1300)).await;
+    assert_eq!(db.get_work_item(id).await.unwrap().state, State::Running);
+    assert!(
+        db.read_from_queue(&queue, 1).await.unwrap().is_none(),
+        "lease expired while the focus was running"
+    );
+
+    wait_for_runs(&db, &[id]).await;
+    assert_eq!(db.get_work_item(id).await.unwrap().attempts, 1);
+
+    control.shutdown();
+    let _ = tokio::time::timeout(std::time::Duration::from_secs(5), handle).await;
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// Two control planes sharing a queue split the work, each item running
+/// exactly once.
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+#[ignore] // requires docker compose up -d
+async fn replicas_share_a_queue() {
+    dotenvy::dotenv().ok();
+    let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let db = Db::connect(&url).await.expect("db connect");
+    db.migrate().await.expect("migrate");
+    let queue = format!(
+        "replicas_{}",
+        &uuid::Uuid::new_v4().simple().to_string()[..8]
+    );
+    db.create_queue(&queue).await.expect("create queue");
+    let db = Arc::new(db);
+
+    let dir = std::env::temp_dir()
+        .join("animus-test")
+        .join(uuid::Uuid::new_v4().to_string());
+    let faculty = write_slow_faculty(&dir);
+    let registry = Arc::new(FacultyRegistry::load_from_dir(&dir).expect("load faculties"));
+
+    let config = ControlConfig {
+        focus_base_dir: dir.join("foci"),
+        visibility_timeout: 1,
+        poll_interval: std::time::Duration::from_millis(100),
+        queues: vec![QueueConfig::new(&queue)],
+        ..ControlConfig::default()
+    };
+    // One focus each, so overlapping runs mean both replicas took work
+    let replicas: Vec<_> = (0..2)
+        .map(|_| ControlPlane::new(Arc::clone(&db), Arc::clone(&registry), config.clone(), 1))
+        .collect();
+    let handles: Vec<_> = replicas
+        .iter()
+        .map(|control| {
+            let ctrl = control.clone();
+            tokio::spawn(async move {
+                ctrl.run().await.expect("control plane run");
+            })
+        })
+        .collect();
+    tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+
+    let ids = [
+        submit_created(&db, NewWorkItem::new(&faculty, "test").queue(&queue)).await,
+        submit_created(&db, NewWorkItem::new(&faculty, "test").queue(&queue)).await,
+    ];
+    let runs = wait_for_runs(&db, &ids).await;
+    for id in ids {
+        assert_eq!(db.get_work_item(id).await.unwrap().attempts, 1);
+    }
+
+    for control in &replicas {
+        control.shutdown();
+    }
+    for handle in handles {
+        let _ = tokio::time::timeout(std::time::Duration::from_secs(5), handle).await;
+    }
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert!(
+        overlap(runs[0], runs[1]),
+        "replicas did not share the work: {runs:?}"
+    );
+}
+
+/// Readiness fails for a missing queue and an empty registry, and passes
+/// once both are in place.
+#[tokio::test]
+#[ignore] // requires docker compose up -d
+async fn health_reports_each_component() {
+    dotenvy::dotenv().ok();
+    let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let db = Db::connect(&url).await.expect("db connect");
+    db.migrate().await.expect("migrate");
+    let db = Arc::new(db);
+    let queue = format!("health_{}", &uuid::Uuid::new_v4().simple().to_string()[..8]);
+    let config = ControlConfig {
+        queues: vec![QueueConfig::new(&queue)],
+        ..ControlConfig::default()
+    };
+
+    let control = ControlPlane::new(
+        Arc::clone(&db),
+        Arc::new(FacultyRegistry::empty()),
+        config.clone(),
+        1,
+    );
+    let report = control.health().await;
+    assert!(!report.ready);
+    assert!(report.component("database").unwrap().ok);
+    assert!(report.component("pgmq").unwrap().ok);
+    assert!(report.component("migrations").unwrap().ok);
+    assert!(!report.component(&format!("queue:{queue}")).unwrap().ok);
+    assert!(!report.component("faculties").unwrap().ok);
+
+    db.create_queue(&queue).await.expect("create queue");
+    let dir = std::env::temp_dir()
+        .join("animus-test")
+        .join(uuid::Uuid::new_v4().to_string());
+    write_slow_faculty(&dir);
+    let registry = FacultyRegistry::load_from_dir(&dir).expect("load faculties");
+    let control = ControlPlane::new(Arc::clone(&db), Arc::new(registry), config, 1);
+    let report = control.health().await;
+    let _ = std::fs::remove_dir_all(&dir);
+    assert!(report.ready, "{report:?}");
+}
@@ -0,0 +1,431 @@
+//! Focus pipeline tests using stub hooks. No database required.
+
+use animus_rs::engine::Focus;
+use animus_rs::engine::focus::FocusResult;
+use animus_rs::faculty::{FacultyMeta, HookConfig, RecoverConfig};
+use animus_rs::model::work::WorkItem;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+
+/// Write an executable shell script and return its path.
+fn write_script(dir: &Path, name: &str, body: &str) -> PathBuf {
+    let path = dir.join(name);
+    std::fs::write(&path, format!("#!/bin/sh\n{body}\n")).unwrap();
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    path
+}
+
+fn stub_faculty(engage: PathBuf, concurrent: bool) -> FacultyMeta {
+    FacultyMeta {
+        name: "stub".to_string(),
+        concurrent,
+        isolation: None,
+        orient: None,
+        engage: HookConfig {
+            command: engage,
+            ..Default::default()
+        },
+        consolidate: None,
+        recover: RecoverConfig {
+            command: PathBuf::from("/bin/true"),
+            max_attempts: 1,
+        },
+        outcome_file: None,
+        outcome_schema: None,
+    }
+}
+
+fn stub_work_item() -> WorkItem {
+    let now = chrono::Utc::now();
+    serde_json::from_value(serde_json::json!({
+        "id": uuid::Uuid::new_v4(),
+        "faculty": "stub",
+        "skill": null,
+        "dedup_key": null,
+        "provenance": { "source": "test", "trigger": null },
+        "params": {},
+        "priority": 0,
+        "state": "running",
+        "merged_into": null,
+        "parent_id": null,
+        "attempts": 0,
+        "max_attempts": null,
+        "created_at": now,
+        "updated_at": now,
+        "resolved_at": null,
+        "outcome": null,
+        "trace_context": null,
+    }))
+    .unwrap()
+}
+
+/// Engage hook that reports the concurrency hints it was given.
+const REPORT_CONCURRENCY: &str = r#"printf '{"max":"%s","faculty_max":"%s"}' \
+  "$ANIMUS_MAX_CONCURRENT" "$ANIMUS_FACULTY_MAX_CONCURRENT" > "$ANIMUS_FOCUS_DIR/engage-out.json""#;
+
+async fn run_concurrency_stub(concurrent: bool, max_concurrent: usize) -> serde_json::Value {
+    let base = std::env::temp_dir()
+        .join("animus-focus-test")
+        .join(uuid::Uuid::new_v4().to_string());
+    std::fs::create_dir_all(&base).unwrap();
+    let engage = write_script(&base, "engage.sh", REPORT_CONCURRENCY);
+    let faculty = stub_faculty(engage, concurrent);
+
+    let focus = Focus::create(&base, stub_work_item())
+        .await
+        .unwrap()
+        .with_max_concurrent(max_concurrent);
+    let result = focus.run(&faculty).await;
+    let _ = std::fs::remove_dir_all(&base);
+
+    match result {
+        FocusResult::Completed { outcome_data, .. } => outcome_data,
+        FocusResult::Failed { phase, error, .. } => panic!("{phase} failed: {error}"),
+    }
+}
+
+#[tokio::test]
+async fn concurrent_faculty_sees_engine_limit() {
+    let out = run_concurrency_stub(true, 8).await;
+    assert_eq!(out["max"], "8");
+    assert_eq!(out["faculty_max"], "8");
+}
+
+#[tokio::test]
+async fn non_concurrent_faculty_is_limited_to_one() {
+    let out = run_concurrency_stub(false, 8).await;
+    assert_eq!(out["max"], "8");
+    assert_eq!(out["faculty_max"], "1");
+}
+
+#[test]
+fn work_env_exports_dedup_key_priority_and_scalar_params() {
+    let mut item = stub_work_item();
+    item.dedup_key = Some("daily-report".to_string());
+    item.priority = 3;
+    item.params = serde_json::json!({
+        "target-user": "kelly",
+        "retries": 2,
+        "dry_run": true,
+        "nested": {"a": 1},
+        "list": [1, 2],
+        "missing": null,
+    });
+
+    let env = animus_rs::engine::work_env(&item);
+    let get = |k: &str| {
+        env.iter()
+            .find(|(key, _)| key == k)
+            .map(|(_, v)| v.as_str())
+    };
+
+    assert_eq!(get("ANIMUS_DEDUP_KEY"), Some("daily-report"));
+    assert_eq!(get("ANIMUS_PRIORITY"), Some("3"));
+    assert_eq!(get("ANIMUS_PARAM_TARGET_USER"), Some("kelly"));
+    assert_eq!(get("ANIMUS_PARAM_RETRIES"), Some("2"));
+    assert_eq!(get("ANIMUS_PARAM_DRY_RUN"), Some("true"));
+    assert_eq!(get("ANIMUS_PARAM_NESTED"), None);
+    assert_eq!(get("ANIMUS_PARAM_LIST"), None);
+    assert_eq!(get("ANIMUS_PARAM_MISSING"), None);
+}
+
+#[tokio::test]
+async fn phases_chain_through_prev_out_and_pipeline_json() {
+    let base = std::env::temp_dir()
+        .join("animus-focus-test")
+        .join(uuid::Uuid::new_v4().to_string());
+    std::fs::create_dir_all(&base).unwrap();
+    let orient = write_script(
+        &base,
+        "orient.sh",
+        r#"echo '{"step":"orient"}' > "$ANIMUS_FOCUS_DIR/orient-out.json""#,
+    );
+    let engage = write_script(
+        &base,
+        "engage.sh",
+        r#"printf '{"prev":"%s","pipeline":%s}' "$ANIMUS_PREV_OUT" "$(cat pipeline.json)" > engage-out.json"#,
+    );
+    let mut faculty = stub_faculty(engage, false);
+    faculty.orient = Some(HookConfig {
+        command: orient,
+        ..Default::default()
+    });
+
+    let focus = Focus::create(&base, stub_work_item()).await.unwrap();
+    let result = focus.run(&faculty).await;
+    let pipeline: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(focus.dir.join("pipeline.json")).unwrap())
+            .unwrap();
+    let _ = std::fs::remove_dir_all(&base);
+
+    let out = match result {
+        FocusResult::Completed { outcome_data, .. } => outcome_data,
+        FocusResult::Failed { phase, error, .. } => panic!("{phase} failed: {error}"),
+    };
+    assert_eq!(
+        out["prev"],
+        focus.dir.join("orient-out.json").display().to_string()
+    );
+    assert_eq!(out["pipeline"]["orient"]["step"], "orient");
+    assert_eq!(pipeline["orient"]["step"], "orient");
+    assert_eq!(pipeline["engage"]["pipeline"]["orient"]["step"], "orient");
+}
+
+#[tokio::test]
+async fn outcome_file_names_the_result() {
+    let base = std::env::temp_dir()
+        .join("animus-focus-test")
+        .join(uuid::Uuid::new_v4().to_string());
+    std::fs::create_dir_all(&base).unwrap();
+    let orient = write_script(
+        &base,
+        "orient.sh",
+        r#"echo '{"from":"orient"}' > "$ANIMUS_FOCUS_DIR/result.json""#,
+    );
+    let engage = write_script(
+        &base,
+        "engage.sh",
+        r#"echo '{"from":"engage"}' > engage-out.json"#,
+    );
+    let mut faculty = stub_faculty(engage, false);
+    faculty.orient = Some(HookConfig {
+        command: orient,
+        ..Default::default()
+    });
+    faculty.outcome_file = Some(PathBuf::from("result.json"));
+
+    let focus = Focus::create(&base, stub_work_item()).await.unwrap();
+    match focus.run(&faculty).await {
+        FocusResult::Completed { outcome_data, .. } => assert_eq!(outcome_data["from"], "orient"),
+        FocusResult::Failed { phase, error, .. } => panic!("{phase} failed: {error}"),
+    }
+
+    faculty.outcome_file = Some(PathBuf::from("missing.json"));
+    let focus = Focus::create(&base, stub_work_item()).await.unwrap();
+    let result = focus.run(&faculty).await;
+    let _ = std::fs::remove_dir_all(&base);
+    match result {
+        FocusResult::Failed { phase, error, .. } => {
+            assert_eq!(phase, "engage");
+            assert!(
+                error.starts_with("outcome file missing.json was not produced"),
+                "{error}"
+            );
+        }
+        FocusResult::Completed { .. } => panic!("missing outcome file should fail"),
+    }
+}
+
+#[tokio::test]
+async fn hooks_get_configured_args_and_env() {
+    let base = std::env::temp_dir()
+        .join("animus-focus-test")
+        .join(uuid::Uuid::new_v4().to_string());
+    std::fs::create_dir_all(&base).unwrap();
+    let engage = write_script(
+        &base,
+        "engage.sh",
+        r#"printf '{"args":"%s","model":"%s","home":"%s"}' "$*" "$MODEL" "$HOOK_HOME" > engage-out.json"#,
+    );
+    let mut faculty = stub_faculty(engage, false);
+    faculty.engage.args = vec![
+        "--model".to_string(),
+        "${ANIMUS_TEST_UNSET_VAR}small".to_string(),
+    ];
+    faculty.engage.env = [
+        ("MODEL".to_string(), "large".to_string()),
+        ("HOOK_HOME".to_string(), "${HOME}/x".to_string()),
+    ]
+    .into();
+
+    let focus = Focus::create(&base, stub_work_item()).await.unwrap();
+    let result = focus.run(&faculty).await;
+    let _ = std::fs::remove_dir_all(&base);
+
+    let out = match result {
+        FocusResult::Completed { outcome_data, .. } => outcome_data,
+        FocusResult::Failed { phase, error, .. } => panic!("{phase} failed: {error}"),
+    };
+    assert_eq!(out["args"], "--model small");
+    assert_eq!(out["model"], "large");
+    assert_eq!(out["home"], format!("{}/x", std::env::var("HOME").unwrap()));
+}
+
+#[tokio::test]
+async fn outcome_that_violates_the_schema_fails_the_focus() {
+    let base = std::env::temp_dir()
+        .join("animus-focus-test")
+        .join(uuid::Uuid::new_v4().to_string());
+    std::fs::create_dir_all(&base).unwrap();
+    let engage = write_script(
+        &base,
+        "engage.sh",
+        r#"echo '{"count":"three"}' > engage-out.json"#,
+    );
+    let mut faculty = stub_faculty(engage, false);
+    faculty.outcome_schema = Some(serde_json::json!({
+        "type": "object",
+        "required": ["count"],
+        "properties": { "count": { "type": "integer" } },
+    }));
+
+    let focus = Focus::create(&base, stub_work_item()).await.unwrap();
+    let result = focus.run(&faculty).await;
+    let _ = std::fs::remove_dir_all(&base);
+
+    match result {
+        FocusResult::Failed { error, .. } => assert_eq!(
+            error,
+            "outcome violates schema: /count: expected integer, got string"
+        ),
+        FocusResult::Completed { .. } => panic!("schema violation should fail"),
+    }
+}
+
+#[tokio::test]
+async fn oversized_params_are_rejected_before_any_directory_is_made() {
+    let base = std::env::temp_dir()
+        .join("animus-focus-test")
+        .join(uuid::Uuid::new_v4().to_string());
+    let mut item = stub_work_item();
+    item.params = serde_json::json!({ "blob": "x".repeat(100) });
+
+    let err = match Focus::create_with_max_params(&base, item.clone(), 64).await {
+        Ok(_) => panic!("oversized params should be rejected"),
+        Err(e) => e,
+    };
+    assert!(err.to_string().contains("over the 64 byte limit"), "{err}");
+    assert!(!base.exists());
+
+    let focus = Focus::create_with_max_params(&base, item, 4096)
+        .await
+        .unwrap();
+    assert!(focus.dir.join("work.json").exists());
+    let _ = std::fs::remove_dir_all(&base);
+}
+
+#[tokio::test]
+async fn hook_over_its_timeout_fails_the_phase() {
+    let base = std::env::temp_dir()
+        .join("animus-focus-test")
+        .join(uuid::Uuid::new_v4().to_string());
+    std::fs::create_dir_all(&base).unwrap();
+    let engage = write_script(&base, "engage.sh", "sleep 5");
+    let mut faculty = stub_faculty(engage, false);
+    faculty.engage.timeout_secs = Some(1);
+
+    let focus = Focus::create(&base, stub_work_item()).await.unwrap();
+    let start = std::time::Instant::now();
+    let result = focus.run(&faculty).await;
+    let _ = std::fs::remove_dir_all(&base);
+
+    assert!(start.elapsed() < std::time::Duration::from_secs(4));
+    match result {
+        FocusResult::Failed { phase, error, .. } => {
+            assert_eq!(phase, "engage");
+            assert_eq!(error, "engage timed out after 1s");
+        }
+        FocusResult::Completed { .. } => panic!("timed-out hook should fail"),
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[tokio::test]
+async fn hook_over_its_cpu_limit_is_killed() {
+    let base = std::env::temp_dir()
+        .join("animus-focus-test")
+        .join(uuid::Uuid::new_v4().to_string());
+    std::fs::create_dir_all(&base).unwrap();
+    let engage = write_script(&base, "engage.sh", "while :; do :; done");
+    let mut faculty = stub_faculty(engage, false);
+    faculty.engage.cpu_time_secs = Some(1);
+    faculty.engage.timeout_secs = Some(10);
+
+    let focus = Focus::create(&base, stub_work_item()).await.unwrap();
+    let start = std::time::Instant::now();
+    let result = focus.run(&faculty).await;
+    let _ = std::fs::remove_dir_all(&base);
+
+    assert!(start.elapsed() < std::time::Duration::from_secs(8));
+    match result {
+        FocusResult::Failed { phase, error, .. } => {
+            assert_eq!(phase, "engage");
+            assert!(!error.contains("timed out"), "{error}");
+        }
+        FocusResult::Completed { .. } => panic!("hook over its CPU limit should fail"),
+    }
+}
+
+#[tokio::test]
+async fn cancelling_kills_the_running_hook() {
+    let base = std::env::temp_dir()
+        .join("animus-focus-test")
+        .join(uuid::Uuid::new_v4().to_string());
+    std::fs::create_dir_all(&base).unwrap();
+    let engage = write_script(&base, "engage.sh", "sleep 5");
+    let faculty = stub_faculty(engage, false);
+
+    let cancel = tokio_util::sync::CancellationToken::new();
+    let focus = Focus::create(&base, stub_work_item())
+        .await
+        .unwrap()
+        .with_cancellation(cancel.clone());
+    let start = std::time::Instant::now();
+    tokio::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+        cancel.cancel();
+    });
+    let result = focus.run(&faculty).await;
+    let _ = std::fs::remove_dir_all(&base);
+
+    assert!(start.elapsed() < std::time::Duration::from_secs(4));
+    match result {
+        FocusResult::Failed { phase, error, .. } => {
+            assert_eq!(phase, "engage");
+            assert_eq!(error, "cancelled");
+        }
+        FocusResult::Completed { .. } => panic!("cancelled focus should fail"),
+    }
+}
+
+#[tokio::test]
+async fn hooks_receive_the_work_span_traceparent() {
+    use opentelemetry::trace::{TraceContextExt as _, TracerProvider as _};
+    use tracing::Instrument as _;
+    use tracing_opentelemetry::OpenTelemetrySpanExt as _;
+    use tracing_subscriber::layer::SubscriberExt as _;
+
+    let provider = opentelemetry_sdk::trace::SdkTracerProvider::builder().build();
+    let subscriber = tracing_subscriber::registry()
+        .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("animus-test")));
+    let _default = tracing::subscriber::set_default(subscriber);
+
+    let base = std::env::temp_dir()
+        .join("animus-focus-test")
+        .join(uuid::Uuid::new_v4().to_string());
+    std::fs::create_dir_all(&base).unwrap();
+    let engage = write_script(
+        &base,
+        "engage.sh",
+        r#"printf '{"traceparent":"%s","span_attributes":{"engage.items":3}}' "$TRACEPARENT" > engage-out.json"#,
+    );
+    let faculty = stub_faculty(engage, false);
+
+    let item = stub_work_item();
+    let span = animus_rs::telemetry::work::start_work_span("stub", &item.id.0);
+    let trace_id = span.context().span().span_context().trace_id();
+    let focus = Focus::create(&base, item).await.unwrap();
+    let result = focus.run(&faculty).instrument(span).await;
+    let _ = std::fs::remove_dir_all(&base);
+
+    let out = match result {
+        FocusResult::Completed { outcome_data, .. } => outcome_data,
+        FocusResult::Failed { phase, error, .. } => panic!("{phase} failed: {error}"),
+    };
+    let traceparent = out["traceparent"].as_str().unwrap();
+    assert_eq!(
+        traceparent.split('-').nth(1),
+        Some(trace_id.to_string().as_str()),
+        "{traceparent}"
+    );
+}
@@ -0,0 +1,118 @@
+//! LLM client construction tests (no network).
+
+use animus_rs::llm::{
+    RetryPolicy, Retryable, anthropic_client, anthropic_client_with_opts, openai_client,
+    openai_compatible_client, with_retry,
+};
+use rig::client::EmbeddingsClient;
+use rig::completion::CompletionError;
+use secrecy::SecretString;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+
+fn fast_policy(max_attempts: u32) -> RetryPolicy {
+    RetryPolicy {
+        max_attempts,
+        initial_backoff: Duration::from_millis(1),
+        max_backoff: Duration::from_millis(5),
+        ..RetryPolicy::default()
+    }
+}
+
+#[test]
+fn anthropic_client_defaults_to_public_api() {
+    let key = SecretString::from("sk-ant-test");
+    let client = anthropic_client(&key).unwrap();
+    assert_eq!(client.base_url(), "https://api.anthropic.com");
+}
+
+#[test]
+fn anthropic_client_with_opts_uses_base_url() {
+    let key = SecretString::from("sk-ant-test");
+    let client = anthropic_client_with_opts(
+        &key,
+        Some("http://localhost:8080"),
+        Some(Duration::from_secs(30)),
+    )
+    .unwrap();
+    assert_eq!(client.base_url(), "http://localhost:8080");
+}
+
+#[test]
+fn openai_clients_provide_embedding_models() {
+    let key = SecretString::from("sk-test");
+    let client = openai_client(&key).unwrap();
+    assert_eq!(client.base_url(), "https://api.openai.com/v1");
+    let _ = client.embedding_model("text-embedding-3-small");
+
+    let local = openai_compatible_client(&key, "http://localhost:11434/v1").unwrap();
+    assert_eq!(local.base_url(), "http://localhost:11434/v1");
+    let _ = local.embedding_model("nomic-embed-text");
+}
+
+#[test]
+fn provider_errors_are_classified() {
+    let overloaded = CompletionError::ProviderError(
+        r#"{"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#.into(),
+    );
+    assert!(overloaded.is_retryable());
+    let limited = CompletionError::ProviderError("Rate limit reached for requests".into());
+    assert!(limited.is_retryable());
+    let invalid = CompletionError::ProviderError(
+        r#"{"type":"error","error":{"type":"invalid_request_error","message":"bad"}}"#.into(),
+    );
+    assert!(!invalid.is_retryable());
+    assert!(!CompletionError::ResponseError("unparseable".into()).is_retryable());
+}
+
+#[test]
+fn backoff_grows_and_is_capped() {
+    let policy = RetryPolicy {
+        initial_backoff: Duration::from_millis(100),
+        max_backoff: Duration::from_millis(1000),
+        ..RetryPolicy::default()
+    };
+    let first = policy.backoff(1);
+    assert!(first >= Duration::from_millis(50) && first <= Duration::from_millis(100));
+    let third = policy.backoff(3);
+    assert!(third >= Duration::from_millis(200) && third <= Duration::from_millis(400));
+    assert!(policy.backoff(20) <= Duration::from_millis(1000));
+}
+
+#[tokio::test]
+async fn with_retry_retries_transient_errors() {
+    let calls = AtomicU32::new(0);
+    let result: Result<String, CompletionError> = with_retry(&fast_policy(5), || async {
+        if calls.fetch_add(1, Ordering::SeqCst) < 2 {
+            Err(CompletionError::ProviderError("overloaded_error".into()))
+        } else {
+            Ok("done".to_string())
+        }
+    })
+    .await;
+    assert_eq!(result.unwrap(), "done");
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn with_retry_stops_on_permanent_errors_and_at_max_attempts() {
+    let calls = AtomicU32::new(0);
+    let result: Result<String, CompletionError> = with_retry(&fast_policy(5), || async {
+        calls.fetch_add(1, Ordering::SeqCst);
+        Err(CompletionError::ProviderError(
+            "invalid_request_error".into(),
+        ))
+    })
+    .await;
+    assert!(result.is_err());
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    let calls = AtomicU32::new(0);
+    let result: Result<String, CompletionError> = with_retry(&fast_policy(3), || async {
+        calls.fetch_add(1, Ordering::SeqCst);
+        Err(CompletionError::ProviderError("rate_limit_error".into()))
+    })
+    .await;
+    assert!(result.is_err());
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+}
@@ -1,5 +1,6 @@
 use animus_rs::db::Db;
-use animus_rs::model::memory::{MemoryFilters, NewMemory};
+use animus_rs::memory::store::identity_rerank;
+use animus_rs::model::memory::{HybridWeights, MemoryFilters, NewMemory};
 use sqlx::PgPool;
 
 fn db_url() -> String {
@@ -70,8 +71,178 @@ async fn hybrid_search_text_and_vector() {
     .unwrap();
 
     let results = db
-        .hybrid_search("morning coffee", &embedding, 10, &MemoryFilters::default())
+        .hybrid_search(
+            "morning coffee",
+            &embedding,
+            10,
+            HybridWeights::default(),
+            &MemoryFilters::default(),
+        )
         .await
         .unwrap();
     assert!(!results.is_empty());
+    assert!(results[0].vector_distance.is_some());
+    assert!(results[0].text_rank.is_some());
+}
+
+#[tokio::test]
+#[ignore] // Requires running Postgres with pgvector
+async fn update_and_delete_memory() {
+    let db = test_db().await;
+
+    let id = db
+        .store_memory(NewMemory {
+            content: "Kelly prefers morning meetings".to_string(),
+            memory_type: "relational".to_string(),
+            source: None,
+            metadata: serde_json::json!({"person": "kelly"}),
+            embedding: axis_embedding(2),
+        })
+        .await
+        .unwrap();
+
+    // Only content changes; metadata is kept
+    let updated = db
+        .update_memory(id, Some("Kelly prefers afternoon meetings"), None, None)
+        .await
+        .unwrap();
+    assert_eq!(updated.content, "Kelly prefers afternoon meetings");
+    assert_eq!(updated.metadata, serde_json::json!({"person": "kelly"}));
+    assert!(updated.updated_at >= updated.created_at);
+
+    let moved = axis_embedding(3);
+    db.update_memory(id, None, None, Some(&moved))
+        .await
+        .unwrap();
+    let results = db
+        .search_memory_by_vector(&moved, 1, &MemoryFilters::default())
+        .await
+        .unwrap();
+    assert_eq!(results[0].id, id);
+
+    assert!(db.delete_memory(id).await.unwrap());
+    assert!(!db.delete_memory(id).await.unwrap());
+    assert!(
+        db.update_memory(id, Some("gone"), None, None)
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+#[ignore] // Requires running Postgres with pgvector
+async fn hybrid_weights_change_ranking() {
+    let db = test_db().await;
+
+    // One memory matches the text, the other the embedding
+    let query = axis_embedding(4);
+    let text_match = db
+        .store_memory(NewMemory {
+            content: "quarterly planning offsite".to_string(),
+            memory_type: "episodic".to_string(),
+            source: None,
+            metadata: serde_json::json!({}),
+            embedding: axis_embedding(5),
+        })
+        .await
+        .unwrap();
+    let vector_match = db
+        .store_memory(NewMemory {
+            content: "unrelated note".to_string(),
+            memory_type: "episodic".to_string(),
+            source: None,
+            metadata: serde_json::json!({}),
+            embedding: query.clone(),
+        })
+        .await
+        .unwrap();
+
+    let search = |weights| {
+        let db = &db;
+        let query = &query;
+        async move {
+            db.hybrid_search(
+                "quarterly planning",
+                query,
+                1,
+                weights,
+                &MemoryFilters::default(),
+            )
+            .await
+        }
+    };
+
+    let by_vector = search(HybridWeights {
+        vector: 1.0,
+        text: 0.0,
+    })
+    .await
+    .unwrap();
+    assert_eq!(by_vector[0].id, vector_match);
+    let by_text = search(HybridWeights {
+        vector: 0.0,
+        text: 1.0,
+    })
+    .await
+    .unwrap();
+    assert_eq!(by_text[0].id, text_match);
+
+    assert!(
+        search(HybridWeights {
+            vector: -1.0,
+            text: 1.0,
+        })
+        .await
+        .is_err()
+    );
+}
+
+#[tokio::test]
+#[ignore] // Requires running Postgres with pgvector
+async fn reranked_search_applies_caller_ordering() {
+    let db = test_db().await;
+
+    // Increasingly far from the query along dimension 7
+    for (content, offset) in [("near", 0.0), ("far", 1.0), ("farther", 3.0)] {
+        let mut embedding = axis_embedding(6);
+        embedding[7] = offset;
+        db.store_memory(NewMemory {
+            content: content.to_string(),
+            memory_type: "episodic".to_string(),
+            source: None,
+            metadata: serde_json::json!({}),
+            embedding,
+        })
+        .await
+        .unwrap();
+    }
+    let query = axis_embedding(6);
+    let filters = MemoryFilters::default();
+    let contents = |entries: Vec<animus_rs::model::memory::MemoryEntry>| {
+        entries.into_iter().map(|e| e.content).collect::<Vec<_>>()
+    };
+
+    let identity = db
+        .search_memory_reranked(&query, 2, 3, identity_rerank, &filters)
+        .await
+        .unwrap();
+    assert_eq!(contents(identity), ["near", "far"]);
+
+    // Reverse the candidates; out-of-range and repeated indices are ignored
+    let reversed = db
+        .search_memory_reranked(
+            &query,
+            2,
+            3,
+            |c| {
+                let mut order = vec![99];
+                order.extend((0..c.len()).rev());
+                order.insert(2, c.len() - 1);
+                order
+            },
+            &filters,
+        )
+        .await
+        .unwrap();
+    assert_eq!(contents(reversed), ["farther", "far"]);
 }
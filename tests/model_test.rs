@@ -0,0 +1,234 @@
+use animus_rs::model::work::{
+    MAX_DEDUP_KEY_LEN, NewWorkItem, Outcome, PriorityLevel, PriorityScheme, State, WorkItem,
+};
+use chrono::{Duration, Utc};
+
+fn work_item(resolved: Option<chrono::DateTime<Utc>>, key: &str) -> WorkItem {
+    let created = Utc::now() - Duration::minutes(10);
+    let updated = Utc::now() - Duration::minutes(2);
+    serde_json::from_value(serde_json::json!({
+        "id": uuid::Uuid::new_v4(),
+        "faculty": "engage",
+        "skill": null,
+        "dedup_key": null,
+        "provenance": { "source": "test", "trigger": null },
+        "params": {},
+        "priority": 0,
+        "state": if resolved.is_some() { "completed" } else { "running" },
+        "merged_into": null,
+        "parent_id": null,
+        "attempts": 1,
+        "max_attempts": null,
+        "created_at": created,
+        "updated_at": updated,
+        key: resolved,
+        "outcome": null,
+        "trace_context": null,
+    }))
+    .unwrap()
+}
+
+#[test]
+fn lifecycle_duration_spans_created_to_resolved() {
+    let resolved = Utc::now() - Duration::minutes(4);
+    let item = work_item(Some(resolved), "resolved_at");
+    assert_eq!(item.lifecycle_duration(), Some(resolved - item.created_at));
+
+    assert_eq!(work_item(None, "resolved_at").lifecycle_duration(), None);
+}
+
+#[test]
+fn completed_at_is_read_as_resolved_at() {
+    let resolved = Utc::now();
+    let item = work_item(Some(resolved), "completed_at");
+    assert_eq!(item.resolved_at, Some(resolved));
+}
+
+#[test]
+fn time_in_current_state_counts_from_last_update() {
+    let item = work_item(None, "resolved_at");
+    let now = item.updated_at + Duration::minutes(2);
+    assert_eq!(item.time_in_current_state(now), Duration::minutes(2));
+}
+
+#[test]
+fn is_stale_uses_the_threshold_for_the_current_state() {
+    use animus_rs::model::work::StalenessPolicy;
+
+    let policy = |claimed: u64, running: u64| StalenessPolicy {
+        claimed_after: std::time::Duration::from_secs(60 * claimed),
+        running_after: std::time::Duration::from_secs(60 * running),
+    };
+    // Running, last updated 2 minutes ago
+    let mut item = work_item(None, "resolved_at");
+    let now = Utc::now();
+    assert!(item.is_stale(&policy(60, 1), now));
+    assert!(!item.is_stale(&policy(1, 5), now));
+
+    item.state = State::Claimed;
+    assert!(item.is_stale(&policy(1, 5), now));
+
+    item.state = State::Queued;
+    assert!(!item.is_stale(&policy(0, 0), now));
+}
+
+#[test]
+fn validate_rejects_blank_faculty_source_and_long_dedup_keys() {
+    assert!(NewWorkItem::new("engage", "user").validate().is_ok());
+
+    let err = NewWorkItem::new("  ", "user").validate().unwrap_err();
+    assert_eq!(err.to_string(), "invalid state: work item faculty is empty");
+    let err = NewWorkItem::new("engage", "").validate().unwrap_err();
+    assert_eq!(err.to_string(), "invalid state: work item source is empty");
+
+    let at_max = NewWorkItem::new("engage", "user").dedup_key("k".repeat(MAX_DEDUP_KEY_LEN));
+    assert!(at_max.validate().is_ok());
+    let too_long = NewWorkItem::new("engage", "user").dedup_key("k".repeat(MAX_DEDUP_KEY_LEN + 1));
+    assert!(too_long.validate().is_err());
+}
+
+#[test]
+fn priority_levels_parse_and_fit_the_default_scheme() {
+    let scheme = PriorityScheme::default();
+    for name in ["low", "normal", "high", "critical"] {
+        let level: PriorityLevel = name.parse().unwrap();
+        assert_eq!(level.to_string(), name);
+        assert!(scheme.check(level.value()).is_ok());
+    }
+    assert!(PriorityLevel::High > PriorityLevel::Normal);
+    assert!("urgent".parse::<PriorityLevel>().is_err());
+
+    let err = scheme.check(1000).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid state: priority 1000 is outside 0..=30"
+    );
+    assert!(scheme.check(-1).is_err());
+}
+
+#[test]
+fn validate_requires_a_dedup_key_with_a_scope() {
+    let scoped = NewWorkItem::new("refresh", "user").dedup_scope("resource");
+    let err = scoped.validate().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid state: dedup scope is set without a dedup key"
+    );
+    assert!(
+        NewWorkItem::new("refresh", "user")
+            .dedup_scope("resource")
+            .dedup_key("r1")
+            .validate()
+            .is_ok()
+    );
+}
+
+#[test]
+fn validate_strict_requires_object_params() {
+    let new = |params| NewWorkItem::new("engage", "user").params(params);
+
+    assert!(new(serde_json::json!({"a": 1})).validate_strict().is_ok());
+    assert!(new(serde_json::Value::Null).validate_strict().is_ok());
+    assert!(new(serde_json::json!([1, 2])).validate().is_ok());
+
+    let err = new(serde_json::json!([1, 2]))
+        .validate_strict()
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid state: work item params must be a JSON object, got an array"
+    );
+}
+
+#[test]
+fn paused_is_reachable_only_from_queued() {
+    assert!(State::Queued.can_transition_to(State::Paused));
+    assert!(State::Paused.can_transition_to(State::Queued));
+    assert!(!State::Paused.can_transition_to(State::Claimed));
+    assert!(!State::Running.can_transition_to(State::Paused));
+    assert!(!State::Paused.is_terminal());
+    assert_eq!("paused".parse::<State>().unwrap(), State::Paused);
+}
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Engage {
+    person: String,
+    turns: u32,
+}
+
+#[test]
+fn typed_params_round_trip() {
+    let params = Engage {
+        person: "kelly".to_string(),
+        turns: 3,
+    };
+    assert!(
+        NewWorkItem::new("engage", "user")
+            .typed_params(&params)
+            .is_ok()
+    );
+
+    let mut item = work_item(None, "resolved_at");
+    item.params = serde_json::to_value(&params).unwrap();
+    assert_eq!(item.params_as::<Engage>().unwrap(), params);
+
+    item.params = serde_json::json!({"person": "kelly"});
+    let err = item.params_as::<Engage>().unwrap_err();
+    assert!(err.to_string().contains("missing field `turns`"), "{err}");
+}
+
+#[test]
+fn outcome_data_as_deserializes_when_present() {
+    let mut outcome = Outcome {
+        success: true,
+        data: None,
+        error: None,
+        duration_ms: 5,
+    };
+    assert_eq!(outcome.data_as::<Engage>().unwrap(), None);
+
+    outcome.data = Some(serde_json::json!({"person": "ana", "turns": 1}));
+    assert_eq!(
+        outcome.data_as::<Engage>().unwrap(),
+        Some(Engage {
+            person: "ana".to_string(),
+            turns: 1
+        })
+    );
+}
+
+#[test]
+fn events_serialize_with_a_flat_kind_tag() {
+    use animus_rs::model::work::{Event, EventKind, WorkId};
+
+    let event = Event {
+        seq: 7,
+        work_id: WorkId::new(),
+        kind: EventKind::StateChanged {
+            from: State::Queued,
+            to: State::Claimed,
+        },
+        created_at: Utc::now(),
+    };
+    let json = serde_json::to_value(&event).unwrap();
+    assert_eq!(json["kind"], "state_changed");
+    assert_eq!(json["from"], "queued");
+    assert_eq!(json["to"], "claimed");
+
+    let back: Event = serde_json::from_value(json).unwrap();
+    assert_eq!(back.kind, event.kind);
+    assert_eq!(event.kind.name(), "state_changed");
+}
+
+#[test]
+fn work_purged_event_carries_the_purge_count() {
+    use animus_rs::model::work::EventKind;
+
+    // purge_completed fills in `count` in SQL, so the shape is fixed
+    let kind = EventKind::WorkPurged { count: 3 };
+    assert_eq!(
+        serde_json::to_value(&kind).unwrap(),
+        serde_json::json!({"kind": "work_purged", "count": 3})
+    );
+    assert_eq!(kind.name(), "work_purged");
+}
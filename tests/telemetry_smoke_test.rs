@@ -22,6 +22,9 @@ fn ensure_telemetry() -> &'static animus_rs::telemetry::TelemetryGuard {
         animus_rs::telemetry::init_telemetry(animus_rs::telemetry::TelemetryConfig {
             endpoint: Some("http://localhost:4317".to_string()),
             service_name: "animus-smoke-test".to_string(),
+            // Export promptly so flush_and_wait mostly waits on the backends
+            scheduled_delay: Some(Duration::from_millis(500)),
+            ..Default::default()
         })
         .expect("failed to init telemetry")
     })
@@ -10,6 +10,7 @@ fn telemetry_initializes_without_endpoint() {
     let config = animus_rs::telemetry::TelemetryConfig {
         endpoint: None,
         service_name: "animus-test".to_string(),
+        ..Default::default()
     };
     // This may return Err if a global subscriber was already set by
     // another test in this process; that is acceptable.
@@ -35,3 +36,55 @@ fn work_span_creates_and_records_transition() {
     let span = animus_rs::telemetry::work::start_work_span("summarize", &id);
     animus_rs::telemetry::work::record_state_transition(&span, "queued", "claimed");
 }
+
+#[test]
+fn submitted_trace_context_becomes_work_span_parent() {
+    use opentelemetry::trace::{TraceContextExt as _, TracerProvider as _};
+    use tracing_opentelemetry::OpenTelemetrySpanExt as _;
+    use tracing_subscriber::layer::SubscriberExt as _;
+
+    let provider = opentelemetry_sdk::trace::SdkTracerProvider::builder().build();
+    let subscriber = tracing_subscriber::registry()
+        .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("animus-test")));
+
+    tracing::subscriber::with_default(subscriber, || {
+        // Submitting side: capture the context of the active span.
+        let submit = tracing::info_span!("submit");
+        let (origin, propagated) = submit.in_scope(|| {
+            (
+                submit.context().span().span_context().clone(),
+                animus_rs::telemetry::work::current_trace_context(),
+            )
+        });
+        assert!(propagated.contains_key("traceparent"));
+
+        // Executing side: the work span joins the submitter's trace.
+        let id = Uuid::new_v4();
+        let span =
+            animus_rs::telemetry::work::start_work_span_with_parent("transform", &id, &propagated);
+        let executed = span.context().span().span_context().clone();
+        assert_eq!(executed.trace_id(), origin.trace_id());
+        assert_ne!(executed.span_id(), origin.span_id());
+    });
+}
+
+#[test]
+fn work_in_state_gauge_registers() {
+    let counts = std::sync::Arc::new(std::sync::RwLock::new(
+        [(animus_rs::model::work::State::Queued, 3)].into(),
+    ));
+    let _gauge = animus_rs::telemetry::metrics::work_in_state(counts);
+}
+
+#[test]
+fn otlp_protocol_parses_standard_names() {
+    use animus_rs::telemetry::OtlpProtocol;
+
+    assert_eq!("grpc".parse::<OtlpProtocol>().unwrap(), OtlpProtocol::Grpc);
+    assert_eq!(
+        "http/protobuf".parse::<OtlpProtocol>().unwrap(),
+        OtlpProtocol::HttpBinary
+    );
+    assert!("http/json".parse::<OtlpProtocol>().is_err());
+    assert_eq!(OtlpProtocol::default(), OtlpProtocol::Grpc);
+}